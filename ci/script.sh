@@ -2,16 +2,16 @@ set -euxo pipefail
 
 main() {
     cargo check -p ufmt --target $T
+    cargo check -p ufmt-utils --target $T
 
-    if [ $TRAVIS_RUST_VERSION = 1.34.0 ]; then
+    if [ $TRAVIS_RUST_VERSION = 1.75.0 ]; then
         return
     fi
 
-    cargo check -p ufmt-utils --target $T
-
     case $T in
         *-unknown-linux-*)
             cargo test --target $T --features std
+            cargo test --target $T -p ufmt-utils --all-features
             ;;
 
         thumbv7m-none-eabi)
@@ -12,7 +12,7 @@ use proc_macro2::{Literal, Span};
 use quote::quote;
 use syn::{parse, parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam, Ident};
 
-use self::write::write;
+use self::write::{write, Macro};
 
 /// Automatically derive the `uDebug` trait for a `struct` or `enum`
 ///
@@ -41,35 +41,46 @@ pub fn debug(input: TokenStream) -> TokenStream {
         Data::Struct(data) => {
             let ident_s = ident.to_string();
 
+            let hint;
             let body = match data.fields {
                 Fields::Named(fields) => {
-                    let fields = fields
+                    let names = fields
                         .named
                         .iter()
-                        .map(|field| {
-                            let ident = field.ident.as_ref().expect("UNREACHABLE");
-                            let name = ident.to_string();
-
-                            quote!(field(#name, &self.#ident)?)
-                        })
+                        .map(|field| field.ident.as_ref().expect("UNREACHABLE").to_string())
+                        .collect::<Vec<_>>();
+                    let idents = fields
+                        .named
+                        .iter()
+                        .map(|field| field.ident.as_ref().expect("UNREACHABLE"))
                         .collect::<Vec<_>>();
 
-                    quote!(f.debug_struct(#ident_s)?#(.#fields)*.finish())
+                    hint = size_hint(
+                        struct_len(&ident_s, &names),
+                        idents.iter().map(|ident| quote!(&self.#ident)),
+                    );
+
+                    quote!(f.debug_struct(#ident_s)?#(.field(#names, &self.#idents)?)*.finish())
                 }
 
                 Fields::Unnamed(fields) => {
-                    let fields = (0..fields.unnamed.len())
-                        .map(|i| {
-                            let i = Literal::u64_unsuffixed(i as u64);
-
-                            quote!(field(&self.#i)?)
-                        })
+                    let indices = (0..fields.unnamed.len())
+                        .map(|i| Literal::u64_unsuffixed(i as u64))
                         .collect::<Vec<_>>();
 
-                    quote!(f.debug_tuple(#ident_s)?#(.#fields)*.finish())
+                    hint = size_hint(
+                        tuple_len(&ident_s, indices.len()),
+                        indices.iter().map(|i| quote!(&self.#i)),
+                    );
+
+                    quote!(f.debug_tuple(#ident_s)?#(.field(&self.#indices)?)*.finish())
                 }
 
-                Fields::Unit => quote!(f.write_str(#ident_s)),
+                Fields::Unit => {
+                    hint = size_hint(ident_s.len(), None);
+
+                    quote!(f.write_str(#ident_s))
+                }
             };
 
             quote!(
@@ -80,13 +91,17 @@ pub fn debug(input: TokenStream) -> TokenStream {
                     {
                         #body
                     }
+
+                    fn size_hint(&self) -> (usize, core::option::Option<usize>) {
+                        #hint
+                    }
                 }
 
             )
         }
 
         Data::Enum(data) => {
-            let arms = data
+            let (arms, hint_arms): (Vec<_>, Vec<_>) = data
                 .variants
                 .iter()
                 .map(|var| {
@@ -96,6 +111,7 @@ pub fn debug(input: TokenStream) -> TokenStream {
                     match &var.fields {
                         Fields::Named(fields) => {
                             let mut pats = Vec::with_capacity(fields.named.len());
+                            let mut names = Vec::with_capacity(fields.named.len());
                             let mut methods = Vec::with_capacity(fields.named.len());
                             for field in &fields.named {
                                 let ident = field.ident.as_ref().unwrap();
@@ -103,12 +119,18 @@ pub fn debug(input: TokenStream) -> TokenStream {
 
                                 pats.push(quote!(#ident));
                                 methods.push(quote!(field(#ident_s, #ident)?));
+                                names.push(ident_s);
                             }
 
-                            quote!(
-                                #ident::#variant { #(#pats),* } => {
-                                    f.debug_struct(#variant_s)?#(.#methods)*.finish()
-                                }
+                            let hint = size_hint(struct_len(&variant_s, &names), pats.clone());
+
+                            (
+                                quote!(
+                                    #ident::#variant { #(#pats),* } => {
+                                        f.debug_struct(#variant_s)?#(.#methods)*.finish()
+                                    }
+                                ),
+                                quote!(#ident::#variant { #(#pats),* } => { #hint }),
                             )
                         }
 
@@ -117,21 +139,36 @@ pub fn debug(input: TokenStream) -> TokenStream {
                                 .map(|i| Ident::new(&format!("_{}", i), Span::call_site()))
                                 .collect::<Vec<_>>();
 
-                            quote!(
-                                #ident::#variant(#(#pats),*) => {
-                                    f.debug_tuple(#variant_s)?#(.field(#pats)?)*.finish()
-                                }
+                            let hint = size_hint(
+                                tuple_len(&variant_s, pats.len()),
+                                pats.iter().map(|pat| quote!(#pat)),
+                            );
+
+                            (
+                                quote!(
+                                    #ident::#variant(#(#pats),*) => {
+                                        f.debug_tuple(#variant_s)?#(.field(#pats)?)*.finish()
+                                    }
+                                ),
+                                quote!(#ident::#variant(#(#pats),*) => { #hint }),
                             )
                         }
 
-                        Fields::Unit => quote!(
-                            #ident::#variant => {
-                                f.write_str(#variant_s)
-                            }
-                        ),
+                        Fields::Unit => {
+                            let hint = size_hint(variant_s.len(), None);
+
+                            (
+                                quote!(
+                                    #ident::#variant => {
+                                        f.write_str(#variant_s)
+                                    }
+                                ),
+                                quote!(#ident::#variant => { #hint }),
+                            )
+                        }
                     }
                 })
-                .collect::<Vec<_>>();
+                .unzip();
 
             quote!(
                 impl #impl_generics ufmt::uDebug for #ident #ty_generics #where_clause {
@@ -143,6 +180,12 @@ pub fn debug(input: TokenStream) -> TokenStream {
                             #(#arms),*
                         }
                     }
+
+                    fn size_hint(&self) -> (usize, core::option::Option<usize>) {
+                        match self {
+                            #(#hint_arms),*
+                        }
+                    }
                 }
             )
         }
@@ -157,25 +200,74 @@ pub fn debug(input: TokenStream) -> TokenStream {
     ts.into()
 }
 
+/// Length of the literal parts of the `debug_struct` output of a struct with the given fields
+fn struct_len(name: &str, fields: &[String]) -> usize {
+    if fields.is_empty() {
+        return name.len();
+    }
+
+    // `Name { a: _, b: _ }`
+    name.len()
+        + " {  }".len()
+        + fields.iter().map(|f| f.len() + ": ".len()).sum::<usize>()
+        + (fields.len() - 1) * ", ".len()
+}
+
+/// Length of the literal parts of the `debug_tuple` output of a tuple struct with `fields` fields
+fn tuple_len(name: &str, fields: usize) -> usize {
+    if fields == 0 {
+        return name.len();
+    }
+
+    // `Name(_, _)`
+    name.len() + "()".len() + (fields - 1) * ", ".len()
+}
+
+/// `uDebug::size_hint` body that adds the size hints of `fields` to `len` bytes of literals
+fn size_hint(
+    len: usize,
+    fields: impl IntoIterator<Item = proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let fields = fields.into_iter();
+
+    quote!(
+        let hint = ufmt::size_hint::exact(#len);
+        #(let hint = ufmt::size_hint::add(hint, ufmt::uDebug::size_hint(#fields));)*
+        hint
+    )
+}
+
 #[proc_macro]
 pub fn uwrite(input: TokenStream) -> TokenStream {
-    write(input, false)
+    write(input, Macro::Uwrite)
 }
 
 #[proc_macro]
 pub fn uwriteln(input: TokenStream) -> TokenStream {
-    write(input, true)
+    write(input, Macro::Uwriteln)
+}
+
+#[proc_macro]
+pub fn uwriteln_crlf(input: TokenStream) -> TokenStream {
+    write(input, Macro::UwritelnCrlf)
+}
+
+#[proc_macro]
+pub fn uwrite_atomic(input: TokenStream) -> TokenStream {
+    write(input, Macro::UwriteAtomic)
+}
+
+#[proc_macro]
+pub fn uwrite_async(input: TokenStream) -> TokenStream {
+    write(input, Macro::UwriteAsync)
+}
+
+#[proc_macro]
+pub fn ulen(input: TokenStream) -> TokenStream {
+    write(input, Macro::Ulen)
 }
 
 #[proc_macro]
 pub fn uformat(input: TokenStream) -> TokenStream {
-    let input = proc_macro2::TokenStream::from(input);
-    let input = quote!(s, #input);
-    let write = proc_macro2::TokenStream::from(write(input.into(), false));
-    quote! {{
-        let mut s = String::new();
-        { #write }.unwrap();
-        s
-    }}
-    .into()
+    write(input, Macro::Uformat)
 }
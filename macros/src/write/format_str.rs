@@ -6,7 +6,7 @@ use syn::parse;
 #[derive(Debug, PartialEq)]
 pub(super) enum FormatArgType {
     Debug { pretty: bool },
-    Display,
+    Display { sign_plus: bool },
 }
 
 #[derive(Debug, PartialEq)]
@@ -58,7 +58,7 @@ fn unescape(mut literal: &str, span: Span) -> parse::Result<Cow<'_, str>> {
     Ok(buf.into())
 }
 
-pub(super) fn parse_format_str(mut literal: &str, span: Span) -> parse::Result<Vec<Piece>> {
+pub(super) fn parse_format_str(mut literal: &str, span: Span) -> parse::Result<Vec<Piece<'_>>> {
     let mut pieces = vec![];
 
     let mut buf = String::new();
@@ -67,6 +67,7 @@ pub(super) fn parse_format_str(mut literal: &str, span: Span) -> parse::Result<V
         const DEBUG: &str = ":?}";
         const DEBUG_PRETTY: &str = ":#?}";
         const DISPLAY: &str = "}";
+        const DISPLAY_SIGN_PLUS: &str = ":+}";
         const ESCAPED_BRACE: &str = "{";
 
         let (implicit_capture, tail) = tail
@@ -89,14 +90,18 @@ pub(super) fn parse_format_str(mut literal: &str, span: Span) -> parse::Result<V
             })
             .or_else(|| {
                 tail.strip_prefix(DISPLAY)
-                    .map(|tail| (FormatArgType::Display, tail))
+                    .map(|tail| (FormatArgType::Display { sign_plus: false }, tail))
+            })
+            .or_else(|| {
+                tail.strip_prefix(DISPLAY_SIGN_PLUS)
+                    .map(|tail| (FormatArgType::Display { sign_plus: true }, tail))
             })
             .map(|(arg_type, tail)| (Some(arg_type), tail))
             .or_else(|| tail.strip_prefix(ESCAPED_BRACE).map(|tail| (None, tail)))
             .ok_or_else(|| {
                 parse::Error::new(
                     span,
-                    "invalid format string: expected `{{`, `{}`, `{:?}` or `{:#?}`",
+                    "invalid format string: expected `{{`, `{}`, `{:+}`, `{:?}` or `{:#?}`",
                 )
             })?;
 
@@ -148,20 +153,34 @@ mod tests {
 
     use super::*;
 
-    fn literal(lit: &str) -> Piece {
+    fn literal(lit: &str) -> Piece<'_> {
         Piece::Literal(Cow::Borrowed(lit))
     }
 
     fn display<'a>() -> Piece<'a> {
         Piece::Arg {
-            arg_type: FormatArgType::Display,
+            arg_type: FormatArgType::Display { sign_plus: false },
             implicit_capture: None,
         }
     }
 
-    fn display_capture(ident: &str) -> Piece {
+    fn display_capture(ident: &str) -> Piece<'_> {
         Piece::Arg {
-            arg_type: FormatArgType::Display,
+            arg_type: FormatArgType::Display { sign_plus: false },
+            implicit_capture: Some(ident),
+        }
+    }
+
+    fn display_sign_plus<'a>() -> Piece<'a> {
+        Piece::Arg {
+            arg_type: FormatArgType::Display { sign_plus: true },
+            implicit_capture: None,
+        }
+    }
+
+    fn display_sign_plus_capture(ident: &str) -> Piece<'_> {
+        Piece::Arg {
+            arg_type: FormatArgType::Display { sign_plus: true },
             implicit_capture: Some(ident),
         }
     }
@@ -173,7 +192,7 @@ mod tests {
         }
     }
 
-    fn debug_capture(ident: &str) -> Piece {
+    fn debug_capture(ident: &str) -> Piece<'_> {
         Piece::Arg {
             arg_type: FormatArgType::Debug { pretty: false },
             implicit_capture: Some(ident),
@@ -187,7 +206,7 @@ mod tests {
         }
     }
 
-    fn debug_pretty_capture(ident: &str) -> Piece {
+    fn debug_pretty_capture(ident: &str) -> Piece<'_> {
         Piece::Arg {
             arg_type: FormatArgType::Debug { pretty: true },
             implicit_capture: Some(ident),
@@ -204,6 +223,12 @@ mod tests {
             vec![display_capture("ident")]
         );
 
+        assert_eq!(parse_format_str("{:+}", span)?, vec![display_sign_plus()]);
+        assert_eq!(
+            parse_format_str("{ident:+}", span)?,
+            vec![display_sign_plus_capture("ident")]
+        );
+
         assert_eq!(parse_format_str("{:?}", span)?, vec![debug()]);
         assert_eq!(
             parse_format_str("{ident:?}", span)?,
@@ -245,6 +270,7 @@ mod tests {
         assert!(parse_format_str("{ ", span).is_err());
         assert!(parse_format_str("{ {", span).is_err());
         assert!(parse_format_str("{:x}", span).is_err());
+        assert!(parse_format_str("{:+?}", span).is_err());
 
         Ok(())
     }
@@ -5,17 +5,24 @@ use syn::{
 };
 
 pub(super) struct Input {
-    pub(super) formatter: Expr,
-    _comma: Token![,],
+    pub(super) formatter: Option<Expr>,
+    _comma: Option<Token![,]>,
     pub(super) literal: LitStr,
     _comma2: Option<Token![,]>,
     pub(super) args: Punctuated<Expr, Token![,]>,
 }
 
-impl Parse for Input {
-    fn parse(input: ParseStream) -> parse::Result<Self> {
-        let formatter = input.parse()?;
-        let _comma = input.parse()?;
+impl Input {
+    /// Parses the input of the macros that don't take a writer, e.g. `uformat!`
+    pub(super) fn parse_without_formatter(input: ParseStream) -> parse::Result<Self> {
+        Self::parse_format_args(None, None, input)
+    }
+
+    fn parse_format_args(
+        formatter: Option<Expr>,
+        _comma: Option<Token![,]>,
+        input: ParseStream,
+    ) -> parse::Result<Self> {
         let literal: LitStr = input.parse()?;
 
         if input.is_empty() {
@@ -37,3 +44,12 @@ impl Parse for Input {
         }
     }
 }
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        let formatter = input.parse()?;
+        let _comma = input.parse()?;
+
+        Self::parse_format_args(Some(formatter), Some(_comma), input)
+    }
+}
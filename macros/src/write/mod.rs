@@ -17,15 +17,40 @@ fn mk_ident(i: usize) -> Ident {
     Ident::new(&format!("__{}", i), Span::call_site())
 }
 
-pub(super) fn write(input: TokenStream, newline: bool) -> TokenStream {
-    let input = parse_macro_input!(input as Input);
+/// The formatting macros, which share the `write` implementation
+#[derive(Clone, Copy)]
+pub(super) enum Macro {
+    Uformat,
+    Ulen,
+    Uwrite,
+    UwriteAsync,
+    UwriteAtomic,
+    Uwriteln,
+    UwritelnCrlf,
+}
+
+pub(super) fn write(input: TokenStream, mac: Macro) -> TokenStream {
+    let input = match mac {
+        Macro::Uformat | Macro::Ulen => {
+            parse_macro_input!(input with Input::parse_without_formatter)
+        }
+        Macro::Uwrite
+        | Macro::UwriteAsync
+        | Macro::UwriteAtomic
+        | Macro::Uwriteln
+        | Macro::UwritelnCrlf => {
+            parse_macro_input!(input as Input)
+        }
+    };
 
     let formatter = &input.formatter;
     let literal = input.literal;
 
     let mut format = literal.value();
-    if newline {
-        format.push('\n');
+    match mac {
+        Macro::Uwriteln => format.push('\n'),
+        Macro::UwritelnCrlf => format.push_str("\r\n"),
+        _ => {}
     }
     let pieces = match parse_format_str(&format, literal.span()) {
         Err(e) => return e.to_compile_error().into(),
@@ -42,7 +67,7 @@ pub(super) fn write(input: TokenStream, newline: bool) -> TokenStream {
         Ordering::Less => {
             return parse::Error::new(
                 literal.span(),
-                &format!(
+                format!(
                     "format string requires {} arguments but {} {} supplied",
                     required_args,
                     supplied_args,
@@ -61,6 +86,7 @@ pub(super) fn write(input: TokenStream, newline: bool) -> TokenStream {
     }
 
     let mut args = vec![];
+    let mut hints = vec![];
     let mut pats = vec![];
     let mut pat_idents = (0..).map(mk_ident);
     let mut arg_exprs = input.args.into_iter();
@@ -68,6 +94,9 @@ pub(super) fn write(input: TokenStream, newline: bool) -> TokenStream {
         .into_iter()
         .map(|piece| match piece {
             Piece::Literal(s) => {
+                let len = s.len();
+                hints.push(quote!(ufmt::size_hint::exact(#len)));
+
                 quote!(f.write_str(#s)?;)
             }
             Piece::Arg {
@@ -88,28 +117,127 @@ pub(super) fn write(input: TokenStream, newline: bool) -> TokenStream {
                     FormatArgType::Debug { pretty } => {
                         let expr = quote!(ufmt::uDebug::fmt(#pat, f));
                         if pretty {
+                            // pretty output is at least as long as the compact one
+                            hints.push(quote!((ufmt::uDebug::size_hint(#pat).0, None)));
+
                             quote!(f.pretty(|f| #expr)?;)
                         } else {
+                            hints.push(quote!(ufmt::uDebug::size_hint(#pat)));
+
                             quote!(#expr?;)
                         }
                     }
-                    FormatArgType::Display => {
-                        quote!(ufmt::uDisplay::fmt(#pat, f)?;)
+                    FormatArgType::Display { sign_plus } => {
+                        let expr = quote!(ufmt::uDisplay::fmt(#pat, f));
+                        if sign_plus {
+                            hints.push(quote!(ufmt::size_hint::add(
+                                ufmt::uDisplay::size_hint(#pat),
+                                (0, Some(1)),
+                            )));
+
+                            quote!(f.with_sign_plus(|f| #expr)?;)
+                        } else {
+                            hints.push(quote!(ufmt::uDisplay::size_hint(#pat)));
+
+                            quote!(#expr?;)
+                        }
                     }
                 }
             }
         })
         .collect::<Vec<_>>();
 
-    quote!(match (#(#args),*) {
-        (#(#pats),*) => {
+    let format = quote!(|f| {
+        #(#exprs)*
+        Ok(())
+    });
+
+    let write = match mac {
+        Macro::Uformat => quote!(
+            use ufmt::UnstableDoAsFormatter as _;
+
+            let hint = ufmt::size_hint::exact(0);
+            #(let hint = ufmt::size_hint::add(hint, #hints);)*
+
+            // only format twice when the length isn't known in advance
+            let capacity = match hint {
+                (lower, Some(upper)) if lower == upper => lower,
+                _ => {
+                    let mut counter = ufmt::LengthCounter::new();
+                    match counter.do_as_formatter(#format) {
+                        Ok(()) => counter.len(),
+                        Err(e) => match e {},
+                    }
+                }
+            };
+
+            let mut s = String::with_capacity(capacity);
+            s.do_as_formatter(#format).unwrap();
+            s
+        ),
+        Macro::Ulen => quote!(
+            use ufmt::UnstableDoAsFormatter as _;
+
+            let mut counter = ufmt::LengthCounter::new();
+            match counter.do_as_formatter(#format) {
+                Ok(()) => counter.len(),
+                Err(e) => match e {},
+            }
+        ),
+        Macro::Uwrite | Macro::Uwriteln | Macro::UwritelnCrlf => quote!(
             use ufmt::UnstableDoAsFormatter as _;
 
-            (#formatter).do_as_formatter(|f| {
-                #(#exprs)*
-                Ok(())
-            })
+            (#formatter).do_as_formatter(#format)
+        ),
+        Macro::UwriteAsync => quote!(
+            use ufmt::{uWriteAsync as _, UnstableDoAsFormatter as _};
+
+            let w = &mut (#formatter);
+            let mut buffer = [0; 64];
+            let mut skip = 0;
+            loop {
+                // format the whole message but only keep the chunk that comes after `skip`
+                let mut window = ufmt::UnstableWindow::new(&mut buffer, skip);
+                let done = window.do_as_formatter(#format).is_ok();
+
+                let chunk = window.as_str();
+                if !chunk.is_empty() {
+                    if let Err(e) = w.write_str(chunk).await {
+                        break Err(e);
+                    }
+                }
+
+                if done {
+                    break Ok(());
+                }
+                skip += chunk.len();
+            }
+        ),
+        Macro::UwriteAtomic => quote!(
+            use ufmt::{uWriteCheckpoint as _, UnstableDoAsFormatter as _};
+
+            let w = &mut (#formatter);
+            let checkpoint = w.checkpoint();
+            match w.do_as_formatter(#format) {
+                Ok(()) => Ok(()),
+                Err(e) => {
+                    w.rollback(checkpoint);
+                    Err(e)
+                }
+            }
+        ),
+    };
+
+    let expr = quote!(match (#(#args),*) {
+        (#(#pats),*) => {
+            #write
         }
-    })
-    .into()
+    });
+
+    if let Macro::UwriteAsync = mac {
+        // the arguments are borrowed by the future
+        quote!(async { #expr }).into()
+    } else {
+        expr.into()
+    }
 }
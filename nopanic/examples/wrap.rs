@@ -0,0 +1,27 @@
+#![no_main]
+#![no_std]
+
+use core::sync::atomic::{AtomicI16, Ordering};
+
+use common::W;
+use cortex_m_rt::{entry, exception};
+use ufmt::uwrite;
+use ufmt_utils::Wrap;
+
+static A: AtomicI16 = AtomicI16::new(0);
+
+#[entry]
+fn main() -> ! {
+    loop {
+        A.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[exception]
+fn PendSV() {
+    let mut w = Wrap::<_, 16>::new(W).max_lines(2);
+    let a = A.load(Ordering::Relaxed);
+
+    uwrite!(&mut w, "Temperature: {} C, status: ok", a).unwrap();
+    w.free();
+}
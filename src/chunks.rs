@@ -0,0 +1,198 @@
+use core::str;
+
+use crate::{uDebug, uDisplay, uWrite, Formatter};
+
+// Implementation detail of the `uwrite_async!` macro: a writer that captures the part of the
+// output that starts `skip` bytes in, rounded up to a `char` boundary, and fits in `buffer`. Once
+// the buffer is full, writes fail to stop the formatting early
+#[doc(hidden)]
+pub struct UnstableWindow<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+    // bytes skipped past `skip` to reach a `char` boundary
+    rounded: usize,
+    skip: usize,
+}
+
+#[doc(hidden)]
+pub struct UnstableWindowFull;
+
+impl<'a> UnstableWindow<'a> {
+    pub fn new(buffer: &'a mut [u8], skip: usize) -> Self {
+        Self {
+            buffer,
+            len: 0,
+            rounded: 0,
+            skip,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // NOTE(unsafe) only whole `char`s are copied into the buffer
+        unsafe { str::from_utf8_unchecked(self.buffer.get_unchecked(..self.len)) }
+    }
+}
+
+impl uWrite for UnstableWindow<'_> {
+    type Error = UnstableWindowFull;
+
+    fn write_str(&mut self, s: &str) -> Result<(), UnstableWindowFull> {
+        if self.skip >= s.len() {
+            self.skip -= s.len();
+            return Ok(());
+        }
+
+        let mut start = self.skip;
+        while !s.is_char_boundary(start) {
+            start += 1;
+        }
+        self.rounded += start - self.skip;
+        self.skip = 0;
+        let s = s.get(start..).unwrap_or_default();
+
+        let room = self.buffer.len() - self.len;
+        let (s, full) = if s.len() <= room {
+            (s, false)
+        } else {
+            let mut end = room;
+            while !s.is_char_boundary(end) {
+                end -= 1;
+            }
+            (s.get(..end).unwrap_or_default(), true)
+        };
+
+        if let Some(dst) = self.buffer.get_mut(self.len..self.len + s.len()) {
+            dst.copy_from_slice(s.as_bytes());
+            self.len += s.len();
+        }
+
+        if full {
+            Err(UnstableWindowFull)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Formats a value into fixed-size chunks, on demand
+///
+/// Each chunk is produced by formatting the value from the start into a writer that discards the
+/// bytes before the chunk and stops once the chunk is full, so a large message can be streamed,
+/// for example to a DMA-driven UART, without a buffer that holds all of it. The value must format
+/// the same way every time.
+///
+/// Chunks always end on a `char` boundary, so a buffer needs room for at least one `char`.
+///
+#[cfg_attr(
+    not(feature = "std"),
+    doc = "This example requires the `std` feature to be enabled:"
+)]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+#[cfg_attr(feature = "std", doc = "```")]
+/// use ufmt::Chunks;
+///
+/// let xs = [1, 20, 300, 4000];
+/// let mut chunks = Chunks::debug(&xs, 0);
+///
+/// let mut buffer = [0; 8];
+/// let mut out = String::new();
+/// while let Some(chunk) = chunks.next_chunk(&mut buffer) {
+///     out.push_str(chunk);
+/// }
+/// assert_eq!(out, "[1, 20, 300, 4000]");
+///
+/// // formatting can start, or resume, anywhere
+/// assert_eq!(Chunks::new("μfmt", 2).next_chunk(&mut buffer), Some("fmt"));
+/// ```
+pub struct Chunks<'a, T>
+where
+    T: ?Sized,
+{
+    done: bool,
+    fmt: fn(&T, &mut UnstableWindow<'_>) -> bool,
+    skip: usize,
+    value: &'a T,
+}
+
+impl<'a, T> Chunks<'a, T>
+where
+    T: ?Sized,
+{
+    /// Formats `value` with `uDisplay`, starting `skip` bytes into the output
+    ///
+    /// If `skip` falls within a `char`, formatting starts at the next one.
+    pub fn new(value: &'a T, skip: usize) -> Self
+    where
+        T: uDisplay,
+    {
+        Self {
+            done: false,
+            fmt: display::<T>,
+            skip,
+            value,
+        }
+    }
+
+    /// Formats `value` with `uDebug`, starting `skip` bytes into the output
+    ///
+    /// If `skip` falls within a `char`, formatting starts at the next one.
+    pub fn debug(value: &'a T, skip: usize) -> Self
+    where
+        T: uDebug,
+    {
+        Self {
+            done: false,
+            fmt: debug::<T>,
+            skip,
+            value,
+        }
+    }
+
+    /// Formats the next chunk into `buffer` and returns it
+    ///
+    /// Returns `None` once the whole value has been formatted, or if `buffer` is too small to hold
+    /// the next `char`; in the latter case a later call with a larger buffer resumes the
+    /// formatting.
+    pub fn next_chunk<'b>(&mut self, buffer: &'b mut [u8]) -> Option<&'b str> {
+        if self.done {
+            return None;
+        }
+
+        let mut window = UnstableWindow::new(buffer, self.skip);
+        self.done = (self.fmt)(self.value, &mut window);
+
+        let UnstableWindow {
+            buffer,
+            len,
+            rounded,
+            ..
+        } = window;
+        self.skip += rounded;
+        if len == 0 {
+            return None;
+        }
+
+        self.skip += len;
+        // NOTE(unsafe) only whole `char`s are copied into the buffer
+        Some(unsafe { str::from_utf8_unchecked(buffer.get_unchecked(..len)) })
+    }
+
+    /// Returns the number of bytes that have been formatted so far, including the skipped ones
+    pub fn position(&self) -> usize {
+        self.skip
+    }
+}
+
+fn display<T>(value: &T, window: &mut UnstableWindow<'_>) -> bool
+where
+    T: uDisplay + ?Sized,
+{
+    value.fmt(&mut Formatter::new(window)).is_ok()
+}
+
+fn debug<T>(value: &T, window: &mut UnstableWindow<'_>) -> bool
+where
+    T: uDebug + ?Sized,
+{
+    value.fmt(&mut Formatter::new(window)).is_ok()
+}
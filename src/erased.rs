@@ -0,0 +1,199 @@
+use core::any::{Any, TypeId};
+
+use crate::{uDebug, uDisplay, uWrite, Formatter};
+
+/// An object-safe version of [`uDebug`], implemented for every `uDebug` type
+///
+/// `uDebug::fmt` is generic over the writer so `dyn uDebug` is not possible; `dyn uDebugDyn` is,
+/// and it implements `uDebug` so it can be used with `{:?}` like any other type. The writer is
+/// erased into a [`DynWriter`], at the cost of dynamic dispatch on every write.
+///
+#[cfg_attr(
+    not(feature = "std"),
+    doc = "This example requires the `std` feature to be enabled:"
+)]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+#[cfg_attr(feature = "std", doc = "```")]
+/// use ufmt::{derive::uDebug, uDebugDyn, uwrite};
+///
+/// #[derive(uDebug)]
+/// struct Reading {
+///     sensor: u8,
+///     value: i16,
+/// }
+///
+/// let fields: [(&str, &dyn uDebugDyn); 2] = [("id", &7u32), ("reading", &Reading { sensor: 1, value: -3 })];
+///
+/// let mut s = String::new();
+/// for (name, value) in fields {
+///     uwrite!(s, "{}={:?};", name, value).unwrap();
+/// }
+/// assert_eq!(s, "id=7;reading=Reading { sensor: 1, value: -3 };");
+/// ```
+#[allow(non_camel_case_types)]
+pub trait uDebugDyn {
+    /// Formats the value using the given type-erased formatter
+    fn fmt_dyn(&self, f: &mut Formatter<'_, DynWriter<'_>>) -> Result<(), DynError>;
+
+    /// Same as [`uDebug::size_hint`]
+    fn size_hint_dyn(&self) -> (usize, Option<usize>);
+}
+
+impl<T> uDebugDyn for T
+where
+    T: uDebug + ?Sized,
+{
+    fn fmt_dyn(&self, f: &mut Formatter<'_, DynWriter<'_>>) -> Result<(), DynError> {
+        uDebug::fmt(self, f)
+    }
+
+    fn size_hint_dyn(&self) -> (usize, Option<usize>) {
+        uDebug::size_hint(self)
+    }
+}
+
+/// An object-safe version of [`uDisplay`], implemented for every `uDisplay` type
+///
+/// See [`uDebugDyn`] for more details
+#[allow(non_camel_case_types)]
+pub trait uDisplayDyn {
+    /// Formats the value using the given type-erased formatter
+    fn fmt_dyn(&self, f: &mut Formatter<'_, DynWriter<'_>>) -> Result<(), DynError>;
+
+    /// Same as [`uDisplay::size_hint`]
+    fn size_hint_dyn(&self) -> (usize, Option<usize>);
+}
+
+impl<T> uDisplayDyn for T
+where
+    T: uDisplay + ?Sized,
+{
+    fn fmt_dyn(&self, f: &mut Formatter<'_, DynWriter<'_>>) -> Result<(), DynError> {
+        uDisplay::fmt(self, f)
+    }
+
+    fn size_hint_dyn(&self) -> (usize, Option<usize>) {
+        uDisplay::size_hint(self)
+    }
+}
+
+impl uDebug for dyn uDebugDyn + '_ {
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        erased(f, |f| self.fmt_dyn(f))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.size_hint_dyn()
+    }
+}
+
+impl uDisplay for dyn uDisplayDyn + '_ {
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        erased(f, |f| self.fmt_dyn(f))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.size_hint_dyn()
+    }
+}
+
+/// Error returned by a [`DynWriter`]; the error of the erased writer is kept aside and returned
+/// once formatting through the trait object is over
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DynError {
+    _private: (),
+}
+
+/// A type-erased writer, used by [`uDebugDyn`] and [`uDisplayDyn`]
+///
+/// The pretty-printing mode and the context of the original formatter are kept.
+pub struct DynWriter<'a> {
+    writer: &'a mut dyn uWrite<Error = DynError>,
+}
+
+impl uWrite for DynWriter<'_> {
+    type Error = DynError;
+
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), DynError> {
+        self.writer.write_str(s)
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> Result<(), DynError> {
+        self.writer.write_char(c)
+    }
+
+    #[inline]
+    fn unstable_context(&self, type_id: TypeId) -> Option<&dyn Any> {
+        self.writer.unstable_context(type_id)
+    }
+}
+
+/// Keeps the error of `writer` aside and reports a `DynError` in its place
+struct Stash<'a, W>
+where
+    W: uWrite + ?Sized,
+{
+    error: Option<W::Error>,
+    writer: &'a mut W,
+}
+
+impl<W> uWrite for Stash<'_, W>
+where
+    W: uWrite + ?Sized,
+{
+    type Error = DynError;
+
+    fn write_str(&mut self, s: &str) -> Result<(), DynError> {
+        self.writer.write_str(s).map_err(|e| {
+            self.error = Some(e);
+            DynError { _private: () }
+        })
+    }
+
+    fn write_char(&mut self, c: char) -> Result<(), DynError> {
+        self.writer.write_char(c).map_err(|e| {
+            self.error = Some(e);
+            DynError { _private: () }
+        })
+    }
+
+    fn unstable_context(&self, type_id: TypeId) -> Option<&dyn Any> {
+        self.writer.unstable_context(type_id)
+    }
+}
+
+/// Runs `f` with a type-erased version of the formatter `f`
+fn erased<W>(
+    f: &mut Formatter<'_, W>,
+    fmt: impl FnOnce(&mut Formatter<'_, DynWriter<'_>>) -> Result<(), DynError>,
+) -> Result<(), W::Error>
+where
+    W: uWrite + ?Sized,
+{
+    let mut stash = Stash {
+        error: None,
+        writer: &mut *f.writer,
+    };
+
+    let result = fmt(&mut Formatter {
+        indentation: f.indentation,
+        max_entries: f.max_entries,
+        pretty: f.pretty,
+        sign_plus: f.sign_plus,
+        writer: &mut DynWriter { writer: &mut stash },
+    });
+
+    match (result, stash.error) {
+        (Err(_), Some(e)) => Err(e),
+        // NOTE a `DynError` can only come from `Stash`, which keeps the original error
+        _ => Ok(()),
+    }
+}
@@ -14,8 +14,11 @@ where
         }
 
         Ok(DebugList {
+            elided: 0,
+            exact: true,
             first: true,
             formatter: self,
+            shown: 0,
         })
     }
 
@@ -29,8 +32,11 @@ where
         }
 
         Ok(DebugMap {
+            elided: 0,
+            exact: true,
             first: true,
             formatter: self,
+            shown: 0,
         })
     }
 
@@ -44,8 +50,11 @@ where
         }
 
         Ok(DebugSet {
+            elided: 0,
+            exact: true,
             first: true,
             formatter: self,
+            shown: 0,
         })
     }
 
@@ -80,6 +89,42 @@ where
             unnamed: name.is_empty(),
         })
     }
+
+    /// Writes the marker that stands for the entries elided due to `Formatter::with_max_entries`
+    fn elision(&mut self, first: bool, elided: usize, exact: bool) -> Result<(), W::Error> {
+        if elided == 0 {
+            return Ok(());
+        }
+
+        if self.pretty {
+            if first {
+                self.write_str("\n")?;
+            }
+
+            self.indent()?;
+        } else if !first {
+            self.write_str(", ")?;
+        }
+
+        if exact {
+            self.write_str("... (")?;
+            // the count is not one of the entries so the `+` flag doesn't apply to it
+            let sign_plus = self.sign_plus;
+            self.sign_plus = false;
+            let result = elided.fmt(self);
+            self.sign_plus = sign_plus;
+            result?;
+            self.write_str(" more)")?;
+        } else {
+            self.write_str("..")?;
+        }
+
+        if self.pretty {
+            self.write_str("\n")?;
+        }
+
+        Ok(())
+    }
 }
 
 /// A struct to help with [`uDebug`] implementations.
@@ -92,8 +137,11 @@ pub struct DebugList<'f, 'w, W>
 where
     W: uWrite + ?Sized,
 {
+    elided: usize,
+    exact: bool,
     first: bool,
     formatter: &'f mut Formatter<'w, W>,
+    shown: usize,
 }
 
 impl<W> DebugList<'_, '_, W>
@@ -102,6 +150,12 @@ where
 {
     /// Adds a new entry to the list output.
     pub fn entry(&mut self, entry: &impl uDebug) -> Result<&mut Self, W::Error> {
+        if self.shown == self.formatter.max_entries {
+            self.elided += 1;
+            return Ok(self);
+        }
+        self.shown += 1;
+
         if self.first {
             self.first = false;
 
@@ -130,7 +184,13 @@ where
         &mut self,
         entries: impl IntoIterator<Item = impl uDebug>,
     ) -> Result<&mut Self, W::Error> {
-        for entry in entries {
+        let mut entries = entries.into_iter();
+        while let Some(entry) = entries.next() {
+            if self.shown == self.formatter.max_entries {
+                self.elide(entries.size_hint());
+                break;
+            }
+
             self.entry(&entry)?;
         }
 
@@ -139,6 +199,9 @@ where
 
     /// Finishes output
     pub fn finish(&mut self) -> Result<(), W::Error> {
+        self.formatter
+            .elision(self.first, self.elided, self.exact)?;
+
         if self.formatter.pretty {
             self.formatter.indentation -= 1;
             self.formatter.indent()?;
@@ -146,6 +209,16 @@ where
 
         self.formatter.write_str("]")
     }
+
+    /// Accounts for the (current and) remaining entries that won't be printed
+    fn elide(&mut self, (lower, upper): (usize, Option<usize>)) {
+        if upper == Some(lower) {
+            self.elided += 1 + lower;
+        } else {
+            self.elided += 1;
+            self.exact = false;
+        }
+    }
 }
 
 /// A struct to help with [`uDebug`] implementations.
@@ -158,8 +231,11 @@ pub struct DebugMap<'f, 'w, W>
 where
     W: uWrite + ?Sized,
 {
+    elided: usize,
+    exact: bool,
     first: bool,
     formatter: &'f mut Formatter<'w, W>,
+    shown: usize,
 }
 
 impl<W> DebugMap<'_, '_, W>
@@ -168,6 +244,12 @@ where
 {
     /// Adds a new entry to the map output.
     pub fn entry(&mut self, key: &impl uDebug, value: &impl uDebug) -> Result<&mut Self, W::Error> {
+        if self.shown == self.formatter.max_entries {
+            self.elided += 1;
+            return Ok(self);
+        }
+        self.shown += 1;
+
         if self.first {
             self.first = false;
 
@@ -198,7 +280,13 @@ where
         &mut self,
         entries: impl IntoIterator<Item = (impl uDebug, impl uDebug)>,
     ) -> Result<&mut Self, W::Error> {
-        for (k, v) in entries.into_iter() {
+        let mut entries = entries.into_iter();
+        while let Some((k, v)) = entries.next() {
+            if self.shown == self.formatter.max_entries {
+                self.elide(entries.size_hint());
+                break;
+            }
+
             self.entry(&k, &v)?;
         }
 
@@ -207,8 +295,21 @@ where
 
     /// Finishes output
     pub fn finish(&mut self) -> Result<(), W::Error> {
+        self.formatter
+            .elision(self.first, self.elided, self.exact)?;
+
         self.formatter.write_str("}")
     }
+
+    /// Accounts for the (current and) remaining entries that won't be printed
+    fn elide(&mut self, (lower, upper): (usize, Option<usize>)) {
+        if upper == Some(lower) {
+            self.elided += 1 + lower;
+        } else {
+            self.elided += 1;
+            self.exact = false;
+        }
+    }
 }
 
 /// A struct to help with [`uDebug`] implementations.
@@ -221,8 +322,11 @@ pub struct DebugSet<'f, 'w, W>
 where
     W: uWrite + ?Sized,
 {
+    elided: usize,
+    exact: bool,
     first: bool,
     formatter: &'f mut Formatter<'w, W>,
+    shown: usize,
 }
 
 impl<W> DebugSet<'_, '_, W>
@@ -231,6 +335,12 @@ where
 {
     /// Adds a new entry to the set output.
     pub fn entry(&mut self, entry: &impl uDebug) -> Result<&mut Self, W::Error> {
+        if self.shown == self.formatter.max_entries {
+            self.elided += 1;
+            return Ok(self);
+        }
+        self.shown += 1;
+
         if self.first {
             self.first = false;
 
@@ -259,7 +369,13 @@ where
         &mut self,
         entries: impl IntoIterator<Item = impl uDebug>,
     ) -> Result<&mut Self, W::Error> {
-        for entry in entries {
+        let mut entries = entries.into_iter();
+        while let Some(entry) = entries.next() {
+            if self.shown == self.formatter.max_entries {
+                self.elide(entries.size_hint());
+                break;
+            }
+
             self.entry(&entry)?;
         }
 
@@ -268,8 +384,21 @@ where
 
     /// Finishes output
     pub fn finish(&mut self) -> Result<(), W::Error> {
+        self.formatter
+            .elision(self.first, self.elided, self.exact)?;
+
         self.formatter.write_str("}")
     }
+
+    /// Accounts for the (current and) remaining entries that won't be printed
+    fn elide(&mut self, (lower, upper): (usize, Option<usize>)) {
+        if upper == Some(lower) {
+            self.elided += 1 + lower;
+        } else {
+            self.elided += 1;
+            self.exact = false;
+        }
+    }
 }
 
 /// A struct to help with [`uDebug`] implementations.
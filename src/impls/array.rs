@@ -1,4 +1,4 @@
-use crate::{uDebug, uWrite, Formatter};
+use crate::{uDebug, uWrite, Formatter, Sorted};
 
 impl<T, const N: usize> uDebug for [T; N]
 where
@@ -11,4 +11,27 @@ where
     {
         <[T] as uDebug>::fmt(self, f)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        <[T] as uDebug>::size_hint(self)
+    }
+}
+
+impl<T, const N: usize> uDebug for Sorted<&'_ [T; N]>
+where
+    T: uDebug + Ord,
+{
+    #[inline]
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        uDebug::fmt(&Sorted(&self.0[..]), f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        <[T] as uDebug>::size_hint(self.0)
+    }
 }
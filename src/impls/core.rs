@@ -1,4 +1,4 @@
-use crate::{uDebug, uDisplay, uWrite, Formatter};
+use crate::{size_hint, sorted::SortedSlice, uDebug, uDisplay, uWrite, Formatter, Sorted};
 
 impl uDebug for bool {
     fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
@@ -11,6 +11,10 @@ impl uDebug for bool {
             f.write_str("false")
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint::exact(if *self { 4 } else { 5 })
+    }
 }
 
 impl uDisplay for bool {
@@ -21,6 +25,11 @@ impl uDisplay for bool {
     {
         <bool as uDebug>::fmt(self, f)
     }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        <bool as uDebug>::size_hint(self)
+    }
 }
 
 // FIXME this (`escape_debug`) contains a panicking branch
@@ -45,6 +54,11 @@ impl uDisplay for char {
     {
         f.write_char(*self)
     }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint::exact(self.len_utf8())
+    }
 }
 
 impl<T> uDebug for [T]
@@ -57,6 +71,34 @@ where
     {
         f.debug_list()?.entries(self)?.finish()
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let entries = self.iter().fold(size_hint::exact(0), |sum, entry| {
+            size_hint::add(sum, entry.size_hint())
+        });
+
+        size_hint::add(
+            size_hint::exact("[]".len()),
+            size_hint::separated(self.len(), ", ", entries),
+        )
+    }
+}
+
+impl<T> uDebug for Sorted<&'_ [T]>
+where
+    T: uDebug + Ord,
+{
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        f.debug_list()?.entries(SortedSlice::new(self.0))?.finish()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        <[T] as uDebug>::size_hint(self.0)
+    }
 }
 
 // FIXME this (`escape_debug`) contains a panicking branch
@@ -100,6 +142,11 @@ impl uDisplay for str {
     {
         f.write_str(self)
     }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint::exact(self.len())
+    }
 }
 
 impl<T> uDebug for &'_ T
@@ -113,6 +160,11 @@ where
     {
         <T as uDebug>::fmt(self, f)
     }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        <T as uDebug>::size_hint(self)
+    }
 }
 
 impl<T> uDisplay for &'_ T
@@ -126,6 +178,11 @@ where
     {
         <T as uDisplay>::fmt(self, f)
     }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        <T as uDisplay>::size_hint(self)
+    }
 }
 
 impl<T> uDebug for &'_ mut T
@@ -139,6 +196,11 @@ where
     {
         <T as uDebug>::fmt(self, f)
     }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        <T as uDebug>::size_hint(self)
+    }
 }
 
 impl<T> uDisplay for &'_ mut T
@@ -152,6 +214,11 @@ where
     {
         <T as uDisplay>::fmt(self, f)
     }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        <T as uDisplay>::size_hint(self)
+    }
 }
 
 impl<T> uDebug for Option<T>
@@ -167,6 +234,13 @@ where
             Some(x) => f.debug_tuple("Some")?.field(x)?.finish(),
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            None => size_hint::exact("None".len()),
+            Some(x) => size_hint::add(size_hint::exact("Some()".len()), x.size_hint()),
+        }
+    }
 }
 
 impl<T, E> uDebug for Result<T, E>
@@ -183,4 +257,11 @@ where
             Ok(x) => f.debug_tuple("Ok")?.field(x)?.finish(),
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Err(e) => size_hint::add(size_hint::exact("Err()".len()), e.size_hint()),
+            Ok(x) => size_hint::add(size_hint::exact("Ok()".len()), x.size_hint()),
+        }
+    }
 }
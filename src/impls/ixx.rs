@@ -13,7 +13,7 @@ macro_rules! ixx {
         let mut n = if negative {
             match n.checked_abs() {
                 Some(n) => n as $uxx,
-                None => core::$uxx::MAX / 2 + 1,
+                None => $uxx::MAX / 2 + 1,
             }
         } else {
             n as $uxx
@@ -40,10 +40,19 @@ macro_rules! impl_ixx {
             where
                 W: uWrite + ?Sized,
             {
+                if f.sign_plus && !self.is_negative() {
+                    f.write_str("+")?;
+                }
+
                 let mut buf = [MaybeUninit::uninit(); $buf_len];
                 f.write_str(ixx!($uty, *self, buf))?;
                 Ok(())
             }
+
+            #[inline(always)]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (1, Some($buf_len))
+            }
         }
 
         impl uDisplay for $ty {
@@ -54,6 +63,11 @@ macro_rules! impl_ixx {
             {
                 <$ty as uDebug>::fmt(self, f)
             }
+
+            #[inline(always)]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                <$ty as uDebug>::size_hint(self)
+            }
         }
     };
 }
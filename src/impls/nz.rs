@@ -16,6 +16,11 @@ macro_rules! nz {
                 {
                     <$inner as uDebug>::fmt(&self.get(), f)
                 }
+
+                #[inline(always)]
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    <$inner as uDebug>::size_hint(&self.get())
+                }
             }
 
             impl uDisplay for $NZ {
@@ -26,6 +31,11 @@ macro_rules! nz {
                 {
                     <$inner as uDisplay>::fmt(&self.get(), f)
                 }
+
+                #[inline(always)]
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    <$inner as uDisplay>::size_hint(&self.get())
+                }
             }
         )*
     }
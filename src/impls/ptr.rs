@@ -47,6 +47,12 @@ impl<T> uDebug for *const T {
         hex!(self, f, 6)
     }
 
+    #[cfg(target_pointer_width = "16")]
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (3, Some(6))
+    }
+
     #[cfg(target_pointer_width = "32")]
     fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
     where
@@ -55,6 +61,12 @@ impl<T> uDebug for *const T {
         hex!(self, f, 10)
     }
 
+    #[cfg(target_pointer_width = "32")]
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (3, Some(10))
+    }
+
     #[cfg(target_pointer_width = "64")]
     fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
     where
@@ -62,6 +74,12 @@ impl<T> uDebug for *const T {
     {
         hex!(self, f, 18)
     }
+
+    #[cfg(target_pointer_width = "64")]
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (3, Some(18))
+    }
 }
 
 impl<T> uDebug for *mut T {
@@ -72,4 +90,9 @@ impl<T> uDebug for *mut T {
     {
         (*self as *const T).fmt(f)
     }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (*self as *const T).size_hint()
+    }
 }
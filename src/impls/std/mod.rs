@@ -2,7 +2,7 @@ mod net;
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
-use crate::{uDebug, uDisplay, uWrite, Formatter};
+use crate::{size_hint, uDebug, uDisplay, uWrite, Formatter, Sorted, SortedByDebug};
 
 impl<T> uDebug for Box<T>
 where
@@ -15,6 +15,11 @@ where
     {
         <T as uDebug>::fmt(self, f)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        <T as uDebug>::size_hint(self)
+    }
 }
 
 impl<T> uDisplay for Box<T>
@@ -28,6 +33,46 @@ where
     {
         <T as uDisplay>::fmt(self, f)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        <T as uDisplay>::size_hint(self)
+    }
+}
+
+/// Size hint of the `uDebug` output of a map with `len` entries
+fn map_size_hint<'a, K, V>(
+    len: usize,
+    entries: impl Iterator<Item = (&'a K, &'a V)>,
+) -> (usize, Option<usize>)
+where
+    K: uDebug + 'a,
+    V: uDebug + 'a,
+{
+    let entries = entries.fold(size_hint::exact(0), |sum, (k, v)| {
+        let entry = size_hint::add(k.size_hint(), v.size_hint());
+        size_hint::add(sum, size_hint::add(entry, size_hint::exact(": ".len())))
+    });
+
+    size_hint::add(
+        size_hint::exact("{}".len()),
+        size_hint::separated(len, ", ", entries),
+    )
+}
+
+/// Size hint of the `uDebug` output of a set with `len` entries
+fn set_size_hint<'a, T>(len: usize, entries: impl Iterator<Item = &'a T>) -> (usize, Option<usize>)
+where
+    T: uDebug + 'a,
+{
+    let entries = entries.fold(size_hint::exact(0), |sum, entry| {
+        size_hint::add(sum, entry.size_hint())
+    });
+
+    size_hint::add(
+        size_hint::exact("{}".len()),
+        size_hint::separated(len, ", ", entries),
+    )
 }
 
 impl<K, V> uDebug for BTreeMap<K, V>
@@ -42,6 +87,11 @@ where
     {
         f.debug_map()?.entries(self)?.finish()
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        map_size_hint(self.len(), self.iter())
+    }
 }
 
 impl<T> uDebug for BTreeSet<T>
@@ -55,6 +105,11 @@ where
     {
         f.debug_set()?.entries(self)?.finish()
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        set_size_hint(self.len(), self.iter())
+    }
 }
 
 impl<K, V, S> uDebug for HashMap<K, V, S>
@@ -69,6 +124,11 @@ where
     {
         f.debug_map()?.entries(self)?.finish()
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        map_size_hint(self.len(), self.iter())
+    }
 }
 
 impl<T, S> uDebug for HashSet<T, S>
@@ -82,6 +142,109 @@ where
     {
         f.debug_set()?.entries(self)?.finish()
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        set_size_hint(self.len(), self.iter())
+    }
+}
+
+impl<K, V, S> uDebug for Sorted<&'_ HashMap<K, V, S>>
+where
+    K: uDebug + Ord,
+    V: uDebug,
+{
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        let mut entries = self.0.iter().collect::<Vec<_>>();
+        entries.sort_unstable_by_key(|(k, _)| *k);
+
+        f.debug_map()?.entries(entries)?.finish()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        map_size_hint(self.0.len(), self.0.iter())
+    }
+}
+
+impl<T, S> uDebug for Sorted<&'_ HashSet<T, S>>
+where
+    T: uDebug + Ord,
+{
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        let mut entries = self.0.iter().collect::<Vec<_>>();
+        entries.sort_unstable();
+
+        f.debug_set()?.entries(entries)?.finish()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        set_size_hint(self.0.len(), self.0.iter())
+    }
+}
+
+/// Sorts the items by the `uDebug` representation of their key
+fn sorted_by_debug<K, T>(items: impl Iterator<Item = T>, key: impl Fn(&T) -> &K) -> Vec<T>
+where
+    K: uDebug + ?Sized,
+{
+    let mut items = items
+        .map(|item| {
+            let mut s = String::new();
+            // NOTE on allocation failure the entry is sorted by a truncated representation
+            uDebug::fmt(key(&item), &mut Formatter::new(&mut s)).ok();
+            (s, item)
+        })
+        .collect::<Vec<_>>();
+    items.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    items.into_iter().map(|(_, item)| item).collect()
+}
+
+impl<K, V, S> uDebug for SortedByDebug<&'_ HashMap<K, V, S>>
+where
+    K: uDebug,
+    V: uDebug,
+{
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        f.debug_map()?
+            .entries(sorted_by_debug(self.0.iter(), |(k, _)| *k))?
+            .finish()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        map_size_hint(self.0.len(), self.0.iter())
+    }
+}
+
+impl<T, S> uDebug for SortedByDebug<&'_ HashSet<T, S>>
+where
+    T: uDebug,
+{
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        f.debug_set()?
+            .entries(sorted_by_debug(self.0.iter(), |t| *t))?
+            .finish()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        set_size_hint(self.0.len(), self.0.iter())
+    }
 }
 
 // TODO
@@ -102,6 +265,11 @@ impl uDisplay for String {
     {
         <str as uDisplay>::fmt(self, f)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        <str as uDisplay>::size_hint(self)
+    }
 }
 
 impl<T> uDebug for Vec<T>
@@ -115,4 +283,9 @@ where
     {
         <[T] as uDebug>::fmt(self, f)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        <[T] as uDebug>::size_hint(self)
+    }
 }
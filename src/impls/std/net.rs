@@ -11,6 +11,11 @@ impl uDisplay for Ipv4Addr {
         let [a, b, c, d] = self.octets();
         uwrite!(f, "{a}.{b}.{c}.{d}")
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        ("0.0.0.0".len(), Some("255.255.255.255".len()))
+    }
 }
 
 impl uDebug for Ipv4Addr {
@@ -21,4 +26,9 @@ impl uDebug for Ipv4Addr {
     {
         uDisplay::fmt(self, f)
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        uDisplay::size_hint(self)
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{uDebug, uWrite, Formatter};
+use crate::{size_hint, uDebug, uWrite, Formatter};
 
 macro_rules! tuple {
     ($($T:ident),*; $($i:tt),*) => {
@@ -12,6 +12,18 @@ macro_rules! tuple {
             {
                 f.debug_tuple("")?$(.field(&self.$i)?)*.finish()
             }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = [$($i),*].len();
+                let fields = size_hint::exact(0);
+                $(let fields = size_hint::add(fields, self.$i.size_hint());)*
+
+                size_hint::add(
+                    // one-element tuples have a trailing comma
+                    size_hint::exact(if len == 1 { "(,)".len() } else { "()".len() }),
+                    size_hint::separated(len, ", ", fields),
+                )
+            }
         }
 
     }
@@ -24,6 +36,10 @@ impl uDebug for () {
     {
         f.write_str("()")
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint::exact("()".len())
+    }
 }
 
 tuple!(A; 0);
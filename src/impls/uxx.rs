@@ -56,10 +56,19 @@ macro_rules! impl_uxx {
             where
                 W: uWrite + ?Sized,
             {
+                if f.sign_plus {
+                    f.write_str("+")?;
+                }
+
                 let mut buf = [MaybeUninit::uninit(); $buf_len];
                 f.write_str(uxx!(*self, buf))?;
                 Ok(())
             }
+
+            #[inline(always)]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (1, Some($buf_len))
+            }
         }
 
         impl uDisplay for $ty {
@@ -70,6 +79,11 @@ macro_rules! impl_uxx {
             {
                 <$ty as uDebug>::fmt(self, f)
             }
+
+            #[inline(always)]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                <$ty as uDebug>::size_hint(self)
+            }
         }
     };
 }
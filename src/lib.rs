@@ -19,6 +19,8 @@
 //! - [`core::fmt::Formatter::debug_struct`][debug_struct]-like API
 //! - [`#[derive(uDebug)]`][derive]
 //! - Pretty formatting (`{:#?}`) for `uDebug`
+//! - Forced sign (`{:+}`) for integers; hand-written impls can query the flags through
+//!   [`Formatter::alternate`] and [`Formatter::sign_plus`]
 //!
 //! [`Debug`]: trait.uDebug.html
 //! [`Display`]: trait.uDisplay.html
@@ -33,7 +35,7 @@
 //!
 //! These are out of scope
 //!
-//! - Padding, alignment and other formatting options
+//! - Padding, alignment, width, precision and other formatting options
 //! - Formatting floating point numbers
 //!
 //! # Examples
@@ -193,7 +195,7 @@
 //!
 //! # Minimum Supported Rust Version (MSRV)
 //!
-//! This crate is guaranteed to compile on stable Rust 1.34 and up. It *might* compile on older
+//! This crate is guaranteed to compile on stable Rust 1.75 and up. It *might* compile on older
 //! versions but that may change in any new patch release.
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -205,9 +207,13 @@
 #[cfg(feature = "std")]
 extern crate self as ufmt;
 
-use core::str;
+use core::{
+    any::{Any, TypeId},
+    convert::Infallible,
+    str,
+};
 
-pub use ufmt_write::uWrite;
+pub use ufmt_write::{uWrite, uWriteAsync, uWriteCheckpoint};
 
 /// Write formatted data into a buffer
 ///
@@ -221,6 +227,7 @@ pub use ufmt_write::uWrite;
 /// [`core::write!`]: https://doc.rust-lang.org/core/macro.write.html
 ///
 /// - `{}` - `uDisplay`
+/// - `{:+}` - `uDisplay` with the `+` flag set (see [`Formatter::sign_plus`])
 /// - `{:?}` - `uDebug`
 /// - `{:#?}` - "pretty" `uDebug`
 ///
@@ -234,14 +241,118 @@ pub use ufmt_macros::uwrite;
 /// See [`uwrite!`](macro.uwrite.html) for more details
 pub use ufmt_macros::uwriteln;
 
+/// Write formatted data into a buffer, with a CRLF (`\r\n`) line ending appended
+///
+/// See [`uwrite!`](macro.uwrite.html) for more details
+pub use ufmt_macros::uwriteln_crlf;
+
+/// Write formatted data into a buffer, discarding the partial output on error
+///
+/// Like [`uwrite!`](macro.uwrite.html) but the writer must implement [`uWriteCheckpoint`]. If any
+/// piece of the message fails to be written the writer is rolled back to the state it had before
+/// the macro call, so it never holds a partially formatted message.
+pub use ufmt_macros::uwrite_atomic;
+
+/// Write formatted data into an asynchronous writer
+///
+/// Accepts the same format string and arguments as [`uwrite!`](macro.uwrite.html) and evaluates
+/// to a future. `uDebug` and `uDisplay` implementations are synchronous, so the message is
+/// formatted in 64-byte chunks, each of which is written with [`uWriteAsync::write_str`] and
+/// awaited before formatting the next one. Every chunk formats the message from the start and
+/// discards the bytes already written, so the arguments must format the same way every time and
+/// writing an `n`-byte message costs `O(n² / 64)` formatting work.
+pub use ufmt_macros::uwrite_async;
+
+/// Returns the number of bytes that formatting the arguments would produce, without writing them
+///
+/// Accepts the same format string and arguments as [`uwrite!`](macro.uwrite.html), minus the
+/// writer; see also [`formatted_len`].
+pub use ufmt_macros::ulen;
+
 #[cfg(feature = "std")]
 /// Write formatted data into a [`String`], like [`format!`].
+///
+/// The `String` is allocated with the exact capacity needed. When the [size
+/// hints](uDebug::size_hint) of the arguments don't give the exact length up front, the arguments
+/// are formatted twice: once into a [`LengthCounter`] and once into the `String`.
 pub use ufmt_macros::uformat;
 
+/// Returns the number of bytes that formatting `value` with `{}` would produce
+///
+/// Nothing is written nor allocated; the value is formatted into a writer that only counts bytes.
+pub fn formatted_len<T>(value: &T) -> usize
+where
+    T: uDisplay + ?Sized,
+{
+    let mut counter = LengthCounter::new();
+    match value.fmt(&mut Formatter::new(&mut counter)) {
+        Ok(()) => counter.len(),
+        Err(e) => match e {},
+    }
+}
+
+/// A writer that discards the data written into it but counts its length in bytes
+///
+/// Useful to size a buffer, or a length prefix, before actually formatting a message.
+///
+/// ```
+/// use ufmt::{uwrite, LengthCounter};
+///
+/// let mut counter = LengthCounter::new();
+/// uwrite!(counter, "{} {:?}", "x =", (-1, 20)).unwrap();
+/// assert_eq!(counter.len(), "x = (-1, 20)".len());
+/// ```
+#[derive(Default)]
+pub struct LengthCounter {
+    len: usize,
+}
+
+impl LengthCounter {
+    /// Creates a new `LengthCounter`
+    pub fn new() -> Self {
+        Self { len: 0 }
+    }
+
+    /// Returns the number of bytes written so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing has been written so far
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl uWrite for LengthCounter {
+    type Error = Infallible;
+
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), Infallible> {
+        self.len += s.len();
+        Ok(())
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> Result<(), Infallible> {
+        self.len += c.len_utf8();
+        Ok(())
+    }
+}
+
+pub use crate::chunks::Chunks;
+pub use crate::erased::{uDebugDyn, uDisplayDyn, DynError, DynWriter};
 pub use crate::helpers::{DebugList, DebugMap, DebugStruct, DebugTuple};
+pub use crate::sorted::Sorted;
+#[cfg(feature = "std")]
+pub use crate::sorted::SortedByDebug;
 
+mod chunks;
+mod erased;
 mod helpers;
 mod impls;
+pub mod size_hint;
+mod sorted;
 /// Derive macros
 pub mod derive {
     pub use ufmt_macros::uDebug;
@@ -254,6 +365,15 @@ pub trait uDebug {
     fn fmt<W>(&self, _: &mut Formatter<'_, W>) -> Result<(), W::Error>
     where
         W: uWrite + ?Sized;
+
+    /// Returns the bounds on the length, in bytes, of the (non-pretty) formatted value
+    ///
+    /// The default implementation returns `(0, None)`. See the [`size_hint`](size_hint/index.html)
+    /// module for helpers to implement this method.
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
 }
 
 /// Just like `core::fmt::Display`
@@ -263,6 +383,15 @@ pub trait uDisplay {
     fn fmt<W>(&self, _: &mut Formatter<'_, W>) -> Result<(), W::Error>
     where
         W: uWrite + ?Sized;
+
+    /// Returns the bounds on the length, in bytes, of the formatted value
+    ///
+    /// The default implementation returns `(0, None)`. See the [`size_hint`](size_hint/index.html)
+    /// module for helpers to implement this method.
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
 }
 
 /// Configuration for formatting
@@ -272,7 +401,9 @@ where
     W: uWrite + ?Sized,
 {
     indentation: u8,
+    max_entries: usize,
     pretty: bool,
+    sign_plus: bool,
     writer: &'w mut W,
 }
 
@@ -285,7 +416,9 @@ where
     pub fn new(writer: &'w mut W) -> Self {
         Self {
             indentation: 0,
+            max_entries: usize::MAX,
             pretty: false,
+            sign_plus: false,
             writer,
         }
     }
@@ -298,9 +431,91 @@ where
     ) -> Result<(), W::Error> {
         let pretty = self.pretty;
         self.pretty = true;
-        f(self)?;
+        let result = f(self);
         self.pretty = pretty;
-        Ok(())
+        result
+    }
+
+    /// Execute the closure with `context` made available to all the nested `uDebug` and
+    /// `uDisplay` implementations through [`Formatter::context`]
+    ///
+    /// The context is encoded in the type of the writer (see [`WithContext`]) so it costs nothing
+    /// to programs that don't use it.
+    #[inline]
+    pub fn with_context<C>(
+        &mut self,
+        context: &C,
+        f: impl FnOnce(&mut Formatter<'_, WithContext<'_, W, C>>) -> Result<(), W::Error>,
+    ) -> Result<(), W::Error>
+    where
+        C: Any,
+    {
+        f(&mut Formatter {
+            indentation: self.indentation,
+            max_entries: self.max_entries,
+            pretty: self.pretty,
+            sign_plus: self.sign_plus,
+            writer: &mut WithContext {
+                context,
+                writer: self.writer,
+            },
+        })
+    }
+
+    /// Returns the innermost context of type `C` installed with [`Formatter::with_context`], if any
+    #[inline]
+    pub fn context<C>(&self) -> Option<&C>
+    where
+        C: Any,
+    {
+        self.writer
+            .unstable_context(TypeId::of::<C>())
+            .and_then(|context| context.downcast_ref())
+    }
+
+    /// Execute the closure with the `+` flag enabled
+    #[inline]
+    pub fn with_sign_plus(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<(), W::Error>,
+    ) -> Result<(), W::Error> {
+        let sign_plus = self.sign_plus;
+        self.sign_plus = true;
+        let result = f(self);
+        self.sign_plus = sign_plus;
+        result
+    }
+
+    /// Determines if the `#` flag was specified, i.e. if pretty-printing (`{:#?}`) is enabled
+    #[inline]
+    pub fn alternate(&self) -> bool {
+        self.pretty
+    }
+
+    /// Determines if the `+` flag was specified (`{:+}`)
+    ///
+    /// The integer implementations of `uDisplay` print a leading `+` for non-negative values when
+    /// this flag is set.
+    #[inline]
+    pub fn sign_plus(&self) -> bool {
+        self.sign_plus
+    }
+
+    /// Execute the closure with at most `max` entries printed per list, map or set
+    ///
+    /// The remaining entries are summarized as `... (N more)`, or as `..` when their number is not
+    /// known in advance (i.e. the iterator passed to `entries` doesn't have an exact size hint).
+    #[inline]
+    pub fn with_max_entries(
+        &mut self,
+        max: usize,
+        f: impl FnOnce(&mut Self) -> Result<(), W::Error>,
+    ) -> Result<(), W::Error> {
+        let max_entries = self.max_entries;
+        self.max_entries = max;
+        let result = f(self);
+        self.max_entries = max_entries;
+        result
     }
 
     /// Writes a character to the underlying buffer contained within this formatter.
@@ -315,8 +530,11 @@ where
         self.writer.write_str(s)
     }
 
-    /// Write whitespace according to the current `self.indentation`
-    fn indent(&mut self) -> Result<(), W::Error> {
+    /// Writes the indentation of the current pretty-printing (`{:#?}`) nesting level
+    ///
+    /// Hand-written `uDebug` implementations that produce multi-line output can call this after
+    /// each newline to line it up with the enclosing value.
+    pub fn indent(&mut self) -> Result<(), W::Error> {
         for _ in 0..self.indentation {
             self.write_str("    ")?;
         }
@@ -325,6 +543,48 @@ where
     }
 }
 
+/// A writer adapter that carries a user-provided context
+///
+/// A [`Formatter`] over this adapter is handed to the closure passed to
+/// [`Formatter::with_context`]; the context can be retrieved with [`Formatter::context`].
+pub struct WithContext<'w, W, C>
+where
+    W: uWrite + ?Sized,
+{
+    context: &'w C,
+    writer: &'w mut W,
+}
+
+impl<W, C> uWrite for WithContext<'_, W, C>
+where
+    W: uWrite + ?Sized,
+    C: Any,
+{
+    type Error = W::Error;
+
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        self.writer.write_str(s)
+    }
+
+    #[inline(always)]
+    fn write_char(&mut self, c: char) -> Result<(), W::Error> {
+        self.writer.write_char(c)
+    }
+
+    #[inline(always)]
+    fn unstable_context(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<C>() {
+            Some(self.context)
+        } else {
+            self.writer.unstable_context(type_id)
+        }
+    }
+}
+
+#[doc(hidden)]
+pub use crate::chunks::{UnstableWindow, UnstableWindowFull};
+
 // Implementation detail of the `uwrite*!` macros
 #[doc(hidden)]
 pub trait UnstableDoAsFormatter {
@@ -362,6 +622,12 @@ where
         &mut self,
         f: impl FnOnce(&mut Formatter<'_, W>) -> Result<(), W::Error>,
     ) -> Result<(), W::Error> {
-        f(self)
+        // like in `core::fmt`, the flags of the value being formatted don't apply to the arguments
+        // of a nested `uwrite!`
+        let sign_plus = self.sign_plus;
+        self.sign_plus = false;
+        let result = f(self);
+        self.sign_plus = sign_plus;
+        result
     }
 }
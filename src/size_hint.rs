@@ -0,0 +1,67 @@
+//! Helpers to compute the size hints of `uDebug` and `uDisplay` implementations
+//!
+//! A size hint is a `(lower, upper)` bound on the number of bytes an implementation writes, like
+//! [`Iterator::size_hint`]. `None` as the upper bound means that it's unknown.
+//!
+//! ```
+//! use ufmt::{size_hint, uDisplay, uWrite, Formatter};
+//!
+//! struct Celsius(i16);
+//!
+//! impl uDisplay for Celsius {
+//!     fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+//!     where
+//!         W: uWrite + ?Sized,
+//!     {
+//!         self.0.fmt(f)?;
+//!         f.write_str(" °C")
+//!     }
+//!
+//!     fn size_hint(&self) -> (usize, Option<usize>) {
+//!         size_hint::add(self.0.size_hint(), size_hint::exact(" °C".len()))
+//!     }
+//! }
+//!
+//! assert_eq!(Celsius(-40).size_hint(), (5, Some(10)));
+//! ```
+
+/// The size hint of output whose length is known
+#[inline]
+pub const fn exact(len: usize) -> (usize, Option<usize>) {
+    (len, Some(len))
+}
+
+/// The size hint of the concatenation of two pieces of output
+#[inline]
+pub const fn add(
+    (a_lower, a_upper): (usize, Option<usize>),
+    (b_lower, b_upper): (usize, Option<usize>),
+) -> (usize, Option<usize>) {
+    let upper = match (a_upper, b_upper) {
+        (Some(a), Some(b)) => a.checked_add(b),
+        _ => None,
+    };
+
+    (a_lower.saturating_add(b_lower), upper)
+}
+
+/// The size hint of a list of entries separated by `separator`
+///
+/// `len` is the number of entries and `entries` the sum of their size hints.
+#[inline]
+pub const fn separated(
+    len: usize,
+    separator: &str,
+    entries: (usize, Option<usize>),
+) -> (usize, Option<usize>) {
+    match len.checked_sub(1) {
+        None => exact(0),
+        Some(separators) => add(
+            entries,
+            match separators.checked_mul(separator.len()) {
+                Some(len) => exact(len),
+                None => (usize::MAX, None),
+            },
+        ),
+    }
+}
@@ -0,0 +1,90 @@
+/// Formats a collection with its entries sorted by their `Ord` implementation
+///
+/// This makes the output of unordered collections like `HashMap` and `HashSet` deterministic:
+///
+#[cfg_attr(
+    not(feature = "std"),
+    doc = "This example requires the `std` feature to be enabled:"
+)]
+#[cfg_attr(not(feature = "std"), doc = "```ignore")]
+#[cfg_attr(feature = "std", doc = "```")]
+/// use std::collections::HashSet;
+///
+/// use ufmt::{uwrite, Sorted};
+///
+/// let set: HashSet<_> = [3, 1, 2].into_iter().collect();
+///
+/// let mut s = String::new();
+/// uwrite!(s, "{:?}", Sorted(&set)).unwrap();
+/// assert_eq!(s, "{1, 2, 3}");
+/// ```
+///
+/// Slices and arrays (`Sorted(&[T])`, `Sorted(&[T; N])`) are also supported, without the `std`
+/// feature. As nothing is allocated, they are sorted with a selection pass per distinct entry, so
+/// formatting them takes a quadratic number of comparisons.
+pub struct Sorted<T>(pub T);
+
+/// Formats a collection with its entries sorted by their `uDebug` representation
+///
+/// Unlike [`Sorted`], this doesn't require the keys to implement `Ord` but each key is formatted
+/// into a temporary `String`.
+#[cfg(feature = "std")]
+pub struct SortedByDebug<T>(pub T);
+
+/// Iterator over the entries of a slice in `Ord` order that doesn't allocate
+pub(crate) struct SortedSlice<'a, T> {
+    items: &'a [T],
+    last: Option<&'a T>,
+    // number of remaining entries equal to `last`
+    repeat: usize,
+    remaining: usize,
+}
+
+impl<'a, T> SortedSlice<'a, T> {
+    pub(crate) fn new(items: &'a [T]) -> Self {
+        Self {
+            items,
+            last: None,
+            repeat: 0,
+            remaining: items.len(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for SortedSlice<'a, T>
+where
+    T: Ord,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.repeat == 0 {
+            // find the smallest entry greater than the last one and count its occurrences
+            let last = self.last;
+            let mut next = None;
+            let greater = |item: &&T| match last {
+                Some(last) => *item > last,
+                None => true,
+            };
+            for item in self.items.iter().filter(greater) {
+                match next {
+                    Some(next) if item > next => {}
+                    Some(next) if item == next => self.repeat += 1,
+                    _ => {
+                        next = Some(item);
+                        self.repeat = 1;
+                    }
+                }
+            }
+            self.last = Some(next?);
+        }
+
+        self.repeat -= 1;
+        self.remaining = self.remaining.saturating_sub(1);
+        self.last
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
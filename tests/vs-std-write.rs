@@ -1,7 +1,11 @@
 use core::convert::Infallible;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
-use ufmt::{derive::uDebug, uDebug, uWrite, uwrite, uwriteln, Formatter};
+use ufmt::{
+    derive::uDebug, uDebug, uDebugDyn, uDisplay, uDisplayDyn, uWrite, uWriteAsync,
+    uWriteCheckpoint, uwrite, uwrite_async, uwrite_atomic, uwriteln, uwriteln_crlf, Chunks,
+    Formatter, Sorted, SortedByDebug,
+};
 
 macro_rules! uformat {
     ($($tt:tt)*) => {{
@@ -59,6 +63,25 @@ fn recursion() {
 
     cmp!("{:?}", x());
     cmp!("{:#?}", x());
+
+    // hand-written impls can recurse through `{:#?}`
+    struct Tree(Option<Box<Tree>>);
+
+    impl uDebug for Tree {
+        fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+        where
+            W: uWrite + ?Sized,
+        {
+            match &self.0 {
+                Some(child) => uwrite!(f, "({:#?})", child),
+                None => f.write_str("leaf"),
+            }
+        }
+    }
+
+    let tree = Tree(Some(Box::new(Tree(Some(Box::new(Tree(None)))))));
+    assert_eq!(uformat!("{:?}", tree).unwrap(), "((leaf))");
+    assert_eq!(uformat!("{:#?}", tree).unwrap(), "((leaf))");
 }
 
 #[test]
@@ -68,12 +91,12 @@ fn uxx() {
     cmp!("{}", 100u8);
 
     // extreme values
-    cmp!("{}", u8::max_value());
-    cmp!("{}", u16::max_value());
-    cmp!("{}", u32::max_value());
-    cmp!("{}", u64::max_value());
-    cmp!("{}", u128::max_value());
-    cmp!("{}", usize::max_value());
+    cmp!("{}", u8::MAX);
+    cmp!("{}", u16::MAX);
+    cmp!("{}", u32::MAX);
+    cmp!("{}", u64::MAX);
+    cmp!("{}", u128::MAX);
+    cmp!("{}", usize::MAX);
 }
 
 #[test]
@@ -84,18 +107,109 @@ fn ixx() {
     cmp!("{}", 100i8);
 
     // extreme values
-    cmp!("{}", i8::min_value());
-    cmp!("{}", i8::max_value());
-    cmp!("{}", i16::min_value());
-    cmp!("{}", i16::max_value());
-    cmp!("{}", i32::min_value());
-    cmp!("{}", i32::max_value());
-    cmp!("{}", i64::min_value());
-    cmp!("{}", i64::max_value());
-    cmp!("{}", i128::min_value());
-    cmp!("{}", i128::max_value());
-    cmp!("{}", isize::min_value());
-    cmp!("{}", isize::max_value());
+    cmp!("{}", i8::MIN);
+    cmp!("{}", i8::MAX);
+    cmp!("{}", i16::MIN);
+    cmp!("{}", i16::MAX);
+    cmp!("{}", i32::MIN);
+    cmp!("{}", i32::MAX);
+    cmp!("{}", i64::MIN);
+    cmp!("{}", i64::MAX);
+    cmp!("{}", i128::MIN);
+    cmp!("{}", i128::MAX);
+    cmp!("{}", isize::MIN);
+    cmp!("{}", isize::MAX);
+}
+
+#[test]
+fn sign_plus() {
+    cmp!("{:+}", 0u8);
+    cmp!("{:+}", 42u32);
+    cmp!("{:+}", u128::MAX);
+
+    cmp!("{:+}", 0i8);
+    cmp!("{:+}", 42i32);
+    cmp!("{:+}", -42i32);
+    cmp!("{:+}", i128::MIN);
+    cmp!("{:+}", i128::MAX);
+
+    // the flag doesn't leak into the following arguments
+    cmp!("{:+} {}", 1, 2);
+
+    // nor into the arguments of a nested `uwrite!`
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    impl uDisplay for Point {
+        fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+        where
+            W: uWrite + ?Sized,
+        {
+            uwrite!(f, "({}, {})", self.x, self.y)
+        }
+    }
+
+    impl core::fmt::Display for Point {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "({}, {})", self.x, self.y)
+        }
+    }
+
+    cmp!("{:+}", Point { x: 1, y: 2 });
+
+    // the flag is restored even if formatting fails
+    struct Full;
+
+    impl uWrite for Full {
+        type Error = ();
+
+        fn write_str(&mut self, _: &str) -> Result<(), ()> {
+            Err(())
+        }
+    }
+
+    let mut full = Full;
+    let mut f = Formatter::new(&mut full);
+    assert_eq!(f.with_sign_plus(|f| uDisplay::fmt(&1, f)), Err(()));
+    assert!(!f.sign_plus());
+}
+
+#[test]
+fn flags() {
+    struct Flags;
+
+    impl uDebug for Flags {
+        fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+        where
+            W: uWrite + ?Sized,
+        {
+            if f.alternate() {
+                f.write_str("alternate")
+            } else {
+                f.write_str("compact")
+            }
+        }
+    }
+
+    impl uDisplay for Flags {
+        fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+        where
+            W: uWrite + ?Sized,
+        {
+            if f.sign_plus() {
+                f.write_str("plus")
+            } else {
+                f.write_str("plain")
+            }
+        }
+    }
+
+    assert_eq!(
+        uformat!("{:?} {:#?} {} {:+}", Flags, Flags, Flags, Flags).unwrap(),
+        "compact alternate plain plus"
+    );
 }
 
 #[test]
@@ -136,6 +250,44 @@ fn set() {
     cmp!("{:#?}", x());
 }
 
+#[test]
+fn sorted() {
+    #[derive(uDebug, PartialEq, Eq, Hash)]
+    enum Key {
+        B,
+        A(u8),
+    }
+
+    let map: HashMap<_, _> = (0..32).map(|i| (i, i * 2)).collect();
+    let btree: BTreeMap<_, _> = map.clone().into_iter().collect();
+    assert_eq!(uformat!("{:?}", Sorted(&map)), uformat!("{:?}", btree));
+    assert_eq!(uformat!("{:#?}", Sorted(&map)), uformat!("{:#?}", btree));
+
+    let set: HashSet<_> = (0..32).collect();
+    let btree: BTreeSet<_> = set.clone().into_iter().collect();
+    assert_eq!(uformat!("{:?}", Sorted(&set)), uformat!("{:?}", btree));
+
+    // slices and arrays are sorted without allocating
+    let mut v = [3, 1, 2, 3, 0, 1];
+    let s = uformat!("{:?}", Sorted(&v)).unwrap();
+    v.sort();
+    assert_eq!(s, uformat!("{:?}", v).unwrap());
+    assert_eq!(uformat!("{:?}", Sorted(&v[..0])).unwrap(), "[]");
+
+    // sorted by representation: `"10"` < `"2"`
+    let map: HashMap<_, _> = [(2, 20), (10, 100), (1, 10)].into_iter().collect();
+    assert_eq!(
+        uformat!("{:?}", SortedByDebug(&map)).unwrap(),
+        "{1: 10, 10: 100, 2: 20}"
+    );
+
+    let set: HashSet<_> = [Key::B, Key::A(1), Key::A(0)].into_iter().collect();
+    assert_eq!(
+        uformat!("{:?}", SortedByDebug(&set)).unwrap(),
+        "{A(0), A(1), B}"
+    );
+}
+
 #[test]
 fn struct_() {
     #[derive(Debug, uDebug)]
@@ -196,6 +348,7 @@ fn enum_() {
 }
 
 #[test]
+#[allow(clippy::manual_dangling_ptr)]
 fn ptr() {
     cmp!("{:?}", 1 as *const u8);
     cmp!("{:?}", 0xf as *const u8);
@@ -243,6 +396,156 @@ fn slice() {
     cmp!("{:#?}", [0, 1]);
 }
 
+#[test]
+fn max_entries() {
+    struct Evens(u32);
+
+    impl uDebug for Evens {
+        fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+        where
+            W: uWrite + ?Sized,
+        {
+            f.debug_list()?
+                .entries((0..self.0).filter(|x| x % 2 == 0))?
+                .finish()
+        }
+    }
+
+    fn capped(max: usize, value: &impl uDebug, pretty: bool) -> String {
+        let mut s = String::new();
+        Formatter::new(&mut s)
+            .with_max_entries(max, |f| {
+                if pretty {
+                    uwrite!(f, "{:#?}", value)
+                } else {
+                    uwrite!(f, "{:?}", value)
+                }
+            })
+            .unwrap();
+        s
+    }
+
+    let v: Vec<u8> = (0..100).collect();
+    assert_eq!(capped(3, &v, false), "[0, 1, 2, ... (97 more)]");
+    assert_eq!(capped(0, &v, false), "[... (100 more)]");
+    assert_eq!(capped(100, &v, false), uformat!("{:?}", v).unwrap());
+    assert_eq!(
+        capped(2, &v, true),
+        "[\n    0,\n    1,\n    ... (98 more)\n]"
+    );
+
+    assert_eq!(capped(2, &Evens(10), false), "[0, 2, ..]");
+    assert_eq!(capped(5, &Evens(10), false), "[0, 2, 4, 6, 8]");
+
+    let mut m = BTreeMap::new();
+    m.insert(1, 2);
+    m.insert(3, 4);
+    m.insert(5, 6);
+    assert_eq!(capped(1, &m, false), "{1: 2, ... (2 more)}");
+
+    let s: BTreeSet<_> = (0..4).collect();
+    assert_eq!(capped(2, &s, false), "{0, 1, ... (2 more)}");
+
+    // the limit applies to each collection separately
+    assert_eq!(
+        capped(1, &[[1, 2], [3, 4]], false),
+        "[[1, ... (1 more)], ... (1 more)]"
+    );
+
+    // the `+` flag applies to the entries but not to the number of elided ones
+    let mut s = String::new();
+    Formatter::new(&mut s)
+        .with_max_entries(1, |f| f.with_sign_plus(|f| uDebug::fmt(&[1, 2, 3], f)))
+        .unwrap();
+    assert_eq!(s, "[+1, ... (2 more)]");
+
+    // the limit is restored even if formatting fails
+    struct FailOnce(Option<String>);
+
+    impl uWrite for FailOnce {
+        type Error = ();
+
+        fn write_str(&mut self, s: &str) -> Result<(), ()> {
+            match &mut self.0 {
+                Some(buffer) => {
+                    buffer.push_str(s);
+                    Ok(())
+                }
+                None => {
+                    self.0 = Some(String::new());
+                    Err(())
+                }
+            }
+        }
+    }
+
+    let mut w = FailOnce(None);
+    let mut f = Formatter::new(&mut w);
+    assert_eq!(
+        f.with_max_entries(1, |f| uwrite!(f, "{:?}", [1, 2])),
+        Err(())
+    );
+    uwrite!(f, "{:?}", [1, 2]).unwrap();
+    assert_eq!(w.0.unwrap(), "[1, 2]");
+}
+
+#[test]
+fn context() {
+    struct SymbolTable(&'static [&'static str]);
+
+    struct Symbol(usize);
+
+    impl uDebug for Symbol {
+        fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+        where
+            W: uWrite + ?Sized,
+        {
+            match f
+                .context::<SymbolTable>()
+                .and_then(|table| table.0.get(self.0))
+            {
+                Some(name) => f.write_str(name),
+                None => f.debug_tuple("Symbol")?.field(&self.0)?.finish(),
+            }
+        }
+    }
+
+    #[derive(uDebug)]
+    struct Call {
+        callee: Symbol,
+        args: Vec<Symbol>,
+    }
+
+    let call = Call {
+        callee: Symbol(0),
+        args: vec![Symbol(1), Symbol(2)],
+    };
+
+    assert_eq!(
+        uformat!("{:?}", call).unwrap(),
+        "Call { callee: Symbol(0), args: [Symbol(1), Symbol(2)] }"
+    );
+
+    let table = SymbolTable(&["print", "x"]);
+    let mut s = String::new();
+    Formatter::new(&mut s)
+        .with_context(&table, |f| uwrite!(f, "{:?} / {:#?}", call, call.callee))
+        .unwrap();
+    assert_eq!(s, "Call { callee: print, args: [x, Symbol(2)] } / print");
+
+    // contexts of different types don't shadow each other
+    let mut s = String::new();
+    Formatter::new(&mut s)
+        .with_context(&table, |f| {
+            f.with_context(&0u8, |f| {
+                assert_eq!(f.context::<u8>(), Some(&0));
+                uwrite!(f, "{:?}", call.callee)
+            })
+        })
+        .unwrap();
+    assert_eq!(s, "print");
+}
+
 #[test]
 fn uwriteln() {
     let mut s = String::new();
@@ -251,6 +554,311 @@ fn uwriteln() {
     assert_eq!(s, "Hello\nWorld\n");
 }
 
+#[test]
+fn uwriteln_crlf() {
+    let mut s = String::new();
+    uwriteln_crlf!(&mut s, "Hello").unwrap();
+    uwriteln_crlf!(&mut s, "{}", 42,).unwrap();
+    assert_eq!(s, "Hello\r\n42\r\n");
+}
+
+#[test]
+fn len() {
+    #[derive(Debug, uDebug)]
+    struct Pair {
+        x: i32,
+        y: i32,
+    }
+
+    let pair = Pair { x: -1, y: 20 };
+    assert_eq!(ufmt::ulen!("{:?}", pair), format!("{:?}", pair).len());
+    assert_eq!(ufmt::ulen!("{:#?}", pair), format!("{:#?}", pair).len());
+    assert_eq!(ufmt::ulen!("{} {}", 'μ', "fmt"), "μ fmt".len());
+    assert_eq!(ufmt::ulen!(""), 0);
+
+    assert_eq!(ufmt::formatted_len(&i64::MIN), i64::MIN.to_string().len());
+    assert_eq!(ufmt::formatted_len("μfmt"), "μfmt".len());
+
+    let s = ufmt::uformat!("{:#?}", pair);
+    assert_eq!(s.capacity(), s.len());
+}
+
+#[test]
+fn size_hint() {
+    macro_rules! check {
+        ($trait:ident, $fmt:literal, $value:expr, $exact:expr) => {{
+            let value = $value;
+            let len = uformat!($fmt, value).unwrap().len();
+            let (lower, upper) = ufmt::$trait::size_hint(&value);
+            assert!(lower <= len, "{} > {} ({})", lower, len, stringify!($value));
+            assert!(
+                upper.map_or(true, |upper| len <= upper),
+                "{:?} < {} ({})",
+                upper,
+                len,
+                stringify!($value)
+            );
+            if $exact {
+                assert_eq!(upper, Some(lower), "{}", stringify!($value));
+            }
+        }};
+    }
+
+    #[derive(uDebug)]
+    struct Unit;
+
+    #[derive(uDebug)]
+    struct Braces {}
+
+    #[derive(uDebug)]
+    struct Tuple(bool, bool);
+
+    #[derive(uDebug)]
+    struct Pair {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(uDebug)]
+    enum X {
+        A,
+        B(u8, bool),
+        C { x: bool, y: u16 },
+    }
+
+    check!(uDisplay, "{}", true, true);
+    check!(uDisplay, "{}", 'μ', true);
+    check!(uDisplay, "{}", "μfmt", true);
+    check!(uDisplay, "{}", String::from("μfmt"), true);
+    check!(uDisplay, "{}", 0u8, false);
+    check!(uDisplay, "{}", u128::MAX, false);
+    check!(uDisplay, "{}", i128::MIN, false);
+    check!(uDisplay, "{}", std::net::Ipv4Addr::BROADCAST, false);
+
+    check!(uDebug, "{:?}", Some(false), true);
+    check!(uDebug, "{:?}", Ok::<bool, ()>(true), true);
+    check!(uDebug, "{:?}", Err::<(), bool>(true), true);
+    check!(uDebug, "{:?}", (), true);
+    check!(uDebug, "{:?}", (true,), true);
+    check!(uDebug, "{:?}", (true, false), true);
+    check!(uDebug, "{:?}", (1, -2, 3), false);
+    check!(uDebug, "{:?}", [true; 0], true);
+    check!(uDebug, "{:?}", [true, false], true);
+    check!(uDebug, "{:?}", vec![i64::MIN, 0], false);
+    check!(uDebug, "{:?}", 0xfff as *const u8, false);
+    check!(uDebug, "{:?}", Unit, true);
+    check!(uDebug, "{:?}", Braces {}, true);
+    check!(uDebug, "{:?}", Tuple(true, false), true);
+    check!(uDebug, "{:?}", Pair { x: -1, y: i32::MIN }, false);
+    check!(uDebug, "{:?}", X::A, true);
+    check!(uDebug, "{:?}", X::B(0, true), false);
+    check!(uDebug, "{:?}", X::C { x: true, y: 0 }, false);
+
+    let map: BTreeMap<_, _> = [(true, false), (false, true)].into_iter().collect();
+    check!(uDebug, "{:?}", map, true);
+    let set: BTreeSet<_> = [1, 22, 333].into_iter().collect();
+    check!(uDebug, "{:?}", set, false);
+}
+
+#[test]
+fn uwrite_atomic() {
+    struct Bounded {
+        buffer: String,
+        capacity: usize,
+    }
+
+    impl uWrite for Bounded {
+        type Error = ();
+
+        fn write_str(&mut self, s: &str) -> Result<(), ()> {
+            if self.buffer.len() + s.len() > self.capacity {
+                return Err(());
+            }
+
+            self.buffer.push_str(s);
+            Ok(())
+        }
+    }
+
+    impl uWriteCheckpoint for Bounded {
+        type Checkpoint = usize;
+
+        fn checkpoint(&self) -> usize {
+            self.buffer.len()
+        }
+
+        fn rollback(&mut self, len: usize) {
+            self.buffer.truncate(len)
+        }
+    }
+
+    let mut w = Bounded {
+        buffer: String::new(),
+        capacity: 16,
+    };
+
+    uwrite_atomic!(&mut w, "{:?}", (1, 2)).unwrap();
+    assert_eq!(uwrite_atomic!(&mut w, "{:?}", [3, 4, 5, 6]), Err(()));
+    assert_eq!(w.buffer, "(1, 2)");
+
+    // whereas `uwrite!` leaves the partial output behind
+    assert_eq!(uwrite!(&mut w, "{:?}", [3, 4, 5, 6]), Err(()));
+    assert_eq!(w.buffer, "(1, 2)[3, 4, 5, ");
+
+    let mut s = String::new();
+    uwrite_atomic!(s, "{}", 42).unwrap();
+    assert_eq!(s, "42");
+}
+
+#[test]
+fn dyn_debug() {
+    #[derive(Debug, uDebug)]
+    struct Pair {
+        x: i32,
+        y: i32,
+    }
+
+    let pair = Pair { x: 1, y: -2 };
+    let values: [&dyn uDebugDyn; 3] = [&pair, &Some(3u8), &[1, 2]];
+    let expected: [&dyn core::fmt::Debug; 3] = [&pair, &Some(3u8), &[1, 2]];
+    for (value, expected) in values.iter().zip(expected) {
+        assert_eq!(uformat!("{:?}", value).unwrap(), format!("{:?}", expected));
+        assert_eq!(
+            uformat!("{:#?}", value).unwrap(),
+            format!("{:#?}", expected)
+        );
+    }
+
+    let values: [&dyn uDisplayDyn; 2] = [&-42i64, &"x"];
+    assert_eq!(uformat!("{}{:+}", values[0], values[1]).unwrap(), "-42x");
+    assert_eq!(uDisplay::size_hint(values[1]), (1, Some(1)));
+
+    // the error of the writer comes out of the trait object
+    struct Full;
+
+    impl uWrite for Full {
+        type Error = &'static str;
+
+        fn write_str(&mut self, _: &str) -> Result<(), &'static str> {
+            Err("full")
+        }
+    }
+
+    let value: &dyn uDebugDyn = &pair;
+    assert_eq!(uwrite!(Full, "{:?}", value), Err("full"));
+}
+
+#[test]
+fn chunks() {
+    struct Pair(&'static str, &'static str);
+
+    impl uDisplay for Pair {
+        fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+        where
+            W: uWrite + ?Sized,
+        {
+            f.write_str(self.0)?;
+            f.write_str(self.1)
+        }
+    }
+
+    let mut buffer = [0; 3];
+    let pair = Pair("μμ", "abc");
+
+    let mut chunks = Chunks::new(&pair, 0);
+    let mut out = String::new();
+    while let Some(chunk) = chunks.next_chunk(&mut buffer) {
+        out.push_str(chunk);
+    }
+    assert_eq!(out, "μμabc");
+    assert_eq!(chunks.position(), "μμabc".len());
+
+    // a `skip` that falls within a `char` starts at the next one
+    let mut chunks = Chunks::new(&pair, 1);
+    assert_eq!(chunks.next_chunk(&mut buffer), Some("μa"));
+    assert_eq!(chunks.position(), 5);
+    assert_eq!(chunks.next_chunk(&mut buffer), Some("bc"));
+
+    // a buffer that's too small for the next `char` doesn't end the formatting
+    let mut chunks = Chunks::new(&pair, 0);
+    assert_eq!(chunks.next_chunk(&mut [0; 1]), None);
+    assert_eq!(chunks.position(), 0);
+    assert_eq!(chunks.next_chunk(&mut buffer), Some("μ"));
+
+    let mut chunks = Chunks::new(&pair, 3);
+    assert_eq!(chunks.next_chunk(&mut buffer), Some("abc"));
+    assert_eq!(chunks.next_chunk(&mut buffer), None);
+    assert_eq!(chunks.position(), "μμabc".len());
+}
+
+#[test]
+fn uwrite_async() {
+    use std::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    /// Completes every write on the second poll
+    struct Slow {
+        chunks: Vec<String>,
+        capacity: usize,
+    }
+
+    impl uWriteAsync for Slow {
+        type Error = ();
+
+        async fn write_str(&mut self, s: &str) -> Result<(), ()> {
+            let mut ready = false;
+            std::future::poll_fn(|cx| {
+                if ready {
+                    Poll::Ready(())
+                } else {
+                    ready = true;
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            })
+            .await;
+
+            if self.chunks.iter().map(String::len).sum::<usize>() + s.len() > self.capacity {
+                return Err(());
+            }
+
+            self.chunks.push(s.to_owned());
+            Ok(())
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    let mut w = Slow {
+        chunks: vec![],
+        capacity: 1024,
+    };
+
+    let xs = (0..40).collect::<Vec<_>>();
+    block_on(uwrite_async!(w, "{:?} μ{}", xs, 1)).unwrap();
+    let expected = format!("{:?} μ{}", xs, 1);
+    assert_eq!(w.chunks.concat(), expected);
+    assert!(w.chunks.len() > 1);
+    assert!(w.chunks.iter().all(|chunk| chunk.len() <= 64));
+
+    block_on(uwrite_async!(&mut w, "")).unwrap();
+    assert_eq!(w.chunks.concat(), expected);
+
+    w.capacity = expected.len() + 1;
+    assert_eq!(block_on(uwrite_async!(w, "{}", 42)), Err(()));
+}
+
 #[test]
 fn formatter_uwrite() {
     #[derive(uDebug)]
@@ -0,0 +1,309 @@
+use core::{convert::Infallible, hash::Hasher};
+
+use ufmt_write::uWrite;
+
+use crate::escape::HEX;
+
+/// A checksum algorithm used by the [`Checksum`] adapter
+pub trait ChecksumAlgorithm: Default {
+    /// Number of hexadecimal digits of the checksum
+    const DIGITS: usize;
+
+    /// Feeds `bytes` into the checksum
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the checksum of the bytes fed so far
+    fn value(&self) -> u32;
+}
+
+/// XOR of all the bytes, as used by NMEA 0183 sentences
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Xor8(u8);
+
+impl ChecksumAlgorithm for Xor8 {
+    const DIGITS: usize = 2;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= byte;
+        }
+    }
+
+    fn value(&self) -> u32 {
+        self.0.into()
+    }
+}
+
+/// CRC-8/SMBUS: polynomial `0x07`, initial value `0`, not reflected
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Crc8(u8);
+
+impl ChecksumAlgorithm for Crc8 {
+    const DIGITS: usize = 2;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= byte;
+            for _ in 0..8 {
+                self.0 = if self.0 & 0x80 != 0 {
+                    (self.0 << 1) ^ 0x07
+                } else {
+                    self.0 << 1
+                };
+            }
+        }
+    }
+
+    fn value(&self) -> u32 {
+        self.0.into()
+    }
+}
+
+/// CRC-16/CCITT-FALSE: polynomial `0x1021`, initial value `0xFFFF`, not reflected
+#[derive(Clone, Copy, Debug)]
+pub struct Crc16(u16);
+
+impl Default for Crc16 {
+    fn default() -> Self {
+        Crc16(0xffff)
+    }
+}
+
+impl ChecksumAlgorithm for Crc16 {
+    const DIGITS: usize = 4;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u16::from(*byte) << 8;
+            for _ in 0..8 {
+                self.0 = if self.0 & 0x8000 != 0 {
+                    (self.0 << 1) ^ 0x1021
+                } else {
+                    self.0 << 1
+                };
+            }
+        }
+    }
+
+    fn value(&self) -> u32 {
+        self.0.into()
+    }
+}
+
+/// CRC-32 (IEEE 802.3): reflected polynomial `0xEDB88320`, initial value and final XOR
+/// `0xFFFFFFFF`
+#[derive(Clone, Copy, Debug)]
+pub struct Crc32(u32);
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Crc32(0xffff_ffff)
+    }
+}
+
+impl ChecksumAlgorithm for Crc32 {
+    const DIGITS: usize = 8;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u32::from(*byte);
+            for _ in 0..8 {
+                self.0 = if self.0 & 1 != 0 {
+                    (self.0 >> 1) ^ 0xedb8_8320
+                } else {
+                    self.0 >> 1
+                };
+            }
+        }
+    }
+
+    fn value(&self) -> u32 {
+        !self.0
+    }
+}
+
+/// A write adapter that computes a checksum of the data it forwards
+///
+/// The checksum can be appended to the output, in uppercase hexadecimal, with
+/// [`write_hex`](Self::write_hex); neither that nor what's written through
+/// [`get_mut`](Self::get_mut) is checksummed.
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{Checksum, SliceWriter, Xor8};
+///
+/// let mut buf = [0; 32];
+/// let mut w = SliceWriter::new(&mut buf);
+///
+/// uwrite!(w, "$").unwrap();
+/// let mut sentence = Checksum::<_, Xor8>::new(&mut w);
+/// uwrite!(sentence, "PMTK{},{}", 220, 100).unwrap();
+/// uwrite!(sentence.get_mut(), "*").unwrap();
+/// sentence.write_hex().unwrap();
+/// assert_eq!(w.as_str(), "$PMTK220,100*2F");
+/// ```
+///
+/// Other algorithms
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{Checksum, Crc16, Crc32, Crc8, SliceWriter};
+///
+/// let mut buf = [0; 32];
+/// let mut w = Checksum::<_, Crc32>::new(SliceWriter::new(&mut buf));
+///
+/// uwrite!(w, "{}", 123456789).unwrap();
+/// assert_eq!(w.value(), 0xCBF43926);
+/// w.write_hex().unwrap();
+/// assert_eq!(w.free().as_str(), "123456789CBF43926");
+///
+/// let mut w = Checksum::<_, Crc16>::new(SliceWriter::new(&mut buf));
+/// uwrite!(w, "{}", 123456789).unwrap();
+/// assert_eq!(w.value(), 0x29B1);
+///
+/// let mut w = Checksum::<_, Crc8>::new(SliceWriter::new(&mut buf));
+/// uwrite!(w, "{}", 123456789).unwrap();
+/// assert_eq!(w.value(), 0xF4);
+/// ```
+pub struct Checksum<W, A>
+where
+    W: uWrite,
+    A: ChecksumAlgorithm,
+{
+    algorithm: A,
+    writer: W,
+}
+
+impl<W, A> Checksum<W, A>
+where
+    W: uWrite,
+    A: ChecksumAlgorithm,
+{
+    /// Creates a new `Checksum` adapter
+    pub fn new(writer: W) -> Self {
+        Self {
+            algorithm: A::default(),
+            writer,
+        }
+    }
+
+    /// Returns the checksum of the data written so far
+    pub fn value(&self) -> u32 {
+        self.algorithm.value()
+    }
+
+    /// Writes the checksum, as `A::DIGITS` uppercase hexadecimal digits, into the underlying
+    /// writer
+    pub fn write_hex(&mut self) -> Result<(), W::Error> {
+        let value = self.value();
+        let mut buf = [0; 8];
+        let digits = A::DIGITS.min(buf.len());
+        for (i, digit) in buf[..digits].iter_mut().rev().enumerate() {
+            *digit = HEX[(value >> (4 * i)) as usize & 0xf];
+        }
+
+        // NOTE only ASCII has been written so this never falls back to the default
+        self.writer
+            .write_str(core::str::from_utf8(&buf[..digits]).unwrap_or_default())
+    }
+
+    /// Returns a mutable reference to the underlying writer, which bypasses the checksum
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Starts a new checksum
+    pub fn reset(&mut self) {
+        self.algorithm = A::default();
+    }
+
+    /// Destroys the adapter and returns the underlying writer
+    pub fn free(self) -> W {
+        self.writer
+    }
+}
+
+impl<W, A> uWrite for Checksum<W, A>
+where
+    W: uWrite,
+    A: ChecksumAlgorithm,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        self.writer.write_str(s)?;
+        self.algorithm.update(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// A writer that feeds the formatted data into a [`Hasher`] without storing it
+///
+/// The data is fed as bytes, so with a streaming hasher the hash doesn't depend on how the
+/// output is split into `write_str` calls.
+///
+/// ```
+/// use core::hash::Hasher;
+///
+/// use ufmt::uwrite;
+/// use ufmt_utils::HashWriter;
+///
+/// // FNV-1a, which hashes the bytes one at a time
+/// struct Fnv(u64);
+///
+/// impl Hasher for Fnv {
+///     fn write(&mut self, bytes: &[u8]) {
+///         for byte in bytes {
+///             self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x100_0000_01b3);
+///         }
+///     }
+///
+///     fn finish(&self) -> u64 {
+///         self.0
+///     }
+/// }
+///
+/// let mut a = HashWriter::new(Fnv(0xcbf2_9ce4_8422_2325));
+/// let mut b = HashWriter::new(Fnv(0xcbf2_9ce4_8422_2325));
+/// uwrite!(a, "temperature: {}", 23).unwrap();
+/// uwrite!(b, "{}: {}", "temperature", 23).unwrap();
+/// assert_eq!(a.finish(), b.finish());
+/// ```
+pub struct HashWriter<H>
+where
+    H: Hasher,
+{
+    hasher: H,
+}
+
+impl<H> HashWriter<H>
+where
+    H: Hasher,
+{
+    /// Creates a new `HashWriter`
+    pub fn new(hasher: H) -> Self {
+        Self { hasher }
+    }
+
+    /// Returns the hash of the data written so far
+    pub fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    /// Destroys the writer and returns the hasher
+    pub fn free(self) -> H {
+        self.hasher
+    }
+}
+
+impl<H> uWrite for HashWriter<H>
+where
+    H: Hasher,
+{
+    type Error = Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Infallible> {
+        self.hasher.write(s.as_bytes());
+        Ok(())
+    }
+}
@@ -0,0 +1,268 @@
+use ufmt_write::uWrite;
+
+/// A write adapter that mirrors the output to two writers
+///
+/// Every string is written to both writers, even if the first one fails.
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{SliceWriter, Tee};
+///
+/// let (mut a, mut b) = ([0; 8], [0; 8]);
+/// let (mut a, mut b) = (SliceWriter::new(&mut a), SliceWriter::new(&mut b));
+///
+/// uwrite!(Tee::new(&mut a, &mut b), "{}", 42).unwrap();
+/// assert_eq!(a.as_str(), "42");
+/// assert_eq!(b.as_str(), "42");
+/// ```
+pub struct Tee<A, B>
+where
+    A: uWrite,
+    B: uWrite,
+{
+    a: A,
+    b: B,
+}
+
+impl<A, B> Tee<A, B>
+where
+    A: uWrite,
+    B: uWrite,
+{
+    /// Creates a new `Tee` adapter
+    pub fn new(a: A, b: B) -> Self {
+        Self { a, b }
+    }
+
+    /// Destroys the adapter and returns the underlying writers
+    pub fn free(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+/// Error returned by [`Tee`]; the error of the first writer takes precedence
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TeeError<A, B> {
+    /// The first writer failed
+    A(A),
+    /// The second writer failed
+    B(B),
+}
+
+impl<A, B> uWrite for Tee<A, B>
+where
+    A: uWrite,
+    B: uWrite,
+{
+    type Error = TeeError<A::Error, B::Error>;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        let a = self.a.write_str(s);
+        let b = self.b.write_str(s);
+
+        a.map_err(TeeError::A)?;
+        b.map_err(TeeError::B)
+    }
+}
+
+/// A write adapter that writes into a secondary writer when the primary one fails
+///
+/// Once the primary writer has failed, everything else is written into the secondary one. A
+/// message can still be split: the part written before the failure stays in the primary writer
+/// and the rest goes into the secondary one, but the two writers never alternate.
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{Fallback, SliceWriter};
+///
+/// let (mut primary, mut secondary) = ([0; 4], [0; 16]);
+/// let mut primary = SliceWriter::new(&mut primary);
+/// let mut secondary = SliceWriter::new(&mut secondary);
+///
+/// uwrite!(Fallback::new(&mut primary, &mut secondary), "{} {} {}", 1, 23456, 7).unwrap();
+/// assert_eq!(primary.as_str(), "1 ");
+/// assert_eq!(secondary.as_str(), "23456 7");
+/// ```
+pub struct Fallback<P, S>
+where
+    P: uWrite,
+    S: uWrite,
+{
+    failed: bool,
+    primary: P,
+    secondary: S,
+}
+
+impl<P, S> Fallback<P, S>
+where
+    P: uWrite,
+    S: uWrite,
+{
+    /// Creates a new `Fallback` adapter
+    pub fn new(primary: P, secondary: S) -> Self {
+        Self {
+            failed: false,
+            primary,
+            secondary,
+        }
+    }
+
+    /// Destroys the adapter and returns the underlying writers
+    pub fn free(self) -> (P, S) {
+        (self.primary, self.secondary)
+    }
+}
+
+impl<P, S> uWrite for Fallback<P, S>
+where
+    P: uWrite,
+    S: uWrite,
+{
+    type Error = S::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), S::Error> {
+        if !self.failed {
+            if self.primary.write_str(s).is_ok() {
+                return Ok(());
+            }
+            self.failed = true;
+        }
+
+        self.secondary.write_str(s)
+    }
+}
+
+/// A write adapter that converts the errors of the underlying writer
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{MapErr, SliceWriter};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Error {
+///     BufferFull,
+/// }
+///
+/// let mut buf = [0; 1];
+/// let mut w = MapErr::new(SliceWriter::new(&mut buf), |_| Error::BufferFull);
+/// assert_eq!(uwrite!(w, "{}", 42), Err(Error::BufferFull));
+/// ```
+pub struct MapErr<W, F>
+where
+    W: uWrite,
+{
+    f: F,
+    writer: W,
+}
+
+impl<W, F> MapErr<W, F>
+where
+    W: uWrite,
+{
+    /// Creates a new `MapErr` adapter that converts errors with `f`
+    pub fn new(writer: W, f: F) -> Self {
+        Self { f, writer }
+    }
+
+    /// Destroys the adapter and returns the underlying writer
+    pub fn free(self) -> W {
+        self.writer
+    }
+}
+
+impl<W, F, E> uWrite for MapErr<W, F>
+where
+    W: uWrite,
+    F: FnMut(W::Error) -> E,
+{
+    type Error = E;
+
+    fn write_str(&mut self, s: &str) -> Result<(), E> {
+        self.writer.write_str(s).map_err(&mut self.f)
+    }
+
+    fn write_char(&mut self, c: char) -> Result<(), E> {
+        self.writer.write_char(c).map_err(&mut self.f)
+    }
+}
+
+/// A write adapter that stops forwarding data after a number of bytes
+///
+/// The output is truncated on a `char` boundary; everything written after the limit is reached is
+/// silently discarded.
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{SliceWriter, Take};
+///
+/// let mut buf = [0; 16];
+/// let mut w = Take::new(SliceWriter::new(&mut buf), 5);
+///
+/// uwrite!(w, "{} μs", 1234).unwrap();
+/// assert!(w.is_truncated());
+/// assert_eq!(w.free().as_str(), "1234 ");
+/// ```
+pub struct Take<W>
+where
+    W: uWrite,
+{
+    remaining: usize,
+    truncated: bool,
+    writer: W,
+}
+
+impl<W> Take<W>
+where
+    W: uWrite,
+{
+    /// Creates a new `Take` adapter that forwards at most `limit` bytes
+    pub fn new(writer: W, limit: usize) -> Self {
+        Self {
+            remaining: limit,
+            truncated: false,
+            writer,
+        }
+    }
+
+    /// Returns the number of bytes that can still be forwarded
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Returns `true` if some of the data has been discarded
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Destroys the adapter and returns the underlying writer
+    pub fn free(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> uWrite for Take<W>
+where
+    W: uWrite,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        if s.len() <= self.remaining {
+            self.remaining -= s.len();
+            return self.writer.write_str(s);
+        }
+
+        self.truncated = true;
+        let mut end = self.remaining;
+        // stop here even if there's room left after the last whole `char`
+        self.remaining = 0;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        match s.get(..end) {
+            Some(head) if !head.is_empty() => self.writer.write_str(head),
+            _ => Ok(()),
+        }
+    }
+}
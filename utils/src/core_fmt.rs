@@ -0,0 +1,183 @@
+use core::fmt;
+
+use ufmt::{uDebug, uDisplay, uWrite, Formatter};
+
+/// An adapter that formats a `uDisplay` / `uDebug` value with `core::fmt`
+///
+/// Implements `fmt::Display` when `T: uDisplay` and `fmt::Debug` when `T: uDebug`; `{:#?}`
+/// selects `ufmt`'s pretty formatting. Other formatting options, like width, are ignored.
+///
+/// ```
+/// use ufmt::derive::uDebug;
+/// use ufmt_utils::AsCoreFmt;
+///
+/// #[derive(uDebug)]
+/// struct Pair {
+///     x: i32,
+///     y: i32,
+/// }
+///
+/// let pair = Pair { x: 1, y: -2 };
+/// assert_eq!(format!("{:?}", AsCoreFmt(&pair)), "Pair { x: 1, y: -2 }");
+/// assert_eq!(format!("{}!", AsCoreFmt(42)), "42!");
+/// ```
+pub struct AsCoreFmt<T>(pub T);
+
+/// Writes into a `core::fmt::Formatter`
+struct CoreWriter<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+impl uWrite for CoreWriter<'_, '_> {
+    type Error = fmt::Error;
+
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        fmt::Write::write_char(self.0, c)
+    }
+}
+
+impl<T> fmt::Display for AsCoreFmt<T>
+where
+    T: uDisplay,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(&mut Formatter::new(&mut CoreWriter(f)))
+    }
+}
+
+impl<T> fmt::Debug for AsCoreFmt<T>
+where
+    T: uDebug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pretty = f.alternate();
+        let mut writer = CoreWriter(f);
+        let mut f = Formatter::new(&mut writer);
+        if pretty {
+            f.pretty(|f| self.0.fmt(f))
+        } else {
+            self.0.fmt(&mut f)
+        }
+    }
+}
+
+/// An adapter that formats a `core::fmt::Display` / `Debug` value with `ufmt`
+///
+/// Implements `uDisplay` when `T: fmt::Display` and `uDebug` when `T: fmt::Debug`; `ufmt`'s `+` flag
+/// selects `{:+}` and its pretty formatting selects `{:#?}`, indented to line up with the enclosing
+/// value. This pulls in the `core::fmt` machinery for `T`. Errors returned by `T`'s own
+/// implementation, rather than by the writer, are ignored.
+///
+/// ```
+/// use ufmt::{derive::uDebug, uwrite};
+/// use ufmt_utils::FromCoreFmt;
+///
+/// let mut s = String::new();
+/// uwrite!(s, "{} {:+} {:?}", FromCoreFmt(1.5), FromCoreFmt(1.5), FromCoreFmt('μ')).unwrap();
+/// assert_eq!(s, "1.5 +1.5 'μ'");
+///
+/// #[derive(uDebug)]
+/// struct Outer {
+///     inner: FromCoreFmt<Option<i32>>,
+/// }
+///
+/// s.clear();
+/// uwrite!(s, "{:#?}", Outer { inner: FromCoreFmt(Some(1)) }).unwrap();
+/// assert_eq!(s, "Outer {\n    inner: Some(\n        1,\n    ),\n}");
+/// ```
+pub struct FromCoreFmt<T>(pub T);
+
+/// Writes into a `ufmt::Formatter`, keeping its error around
+///
+/// When pretty-printing, lines after the first one are indented to the formatter's nesting level.
+struct UfmtWriter<'a, 'w, W>
+where
+    W: uWrite + ?Sized,
+{
+    error: Option<W::Error>,
+    formatter: &'a mut Formatter<'w, W>,
+    // a newline has been written and the next line hasn't been indented yet
+    newline: bool,
+}
+
+impl<W> UfmtWriter<'_, '_, W>
+where
+    W: uWrite + ?Sized,
+{
+    fn write_line(&mut self, line: &str) -> Result<(), W::Error> {
+        if self.newline {
+            self.newline = false;
+            self.formatter.indent()?;
+        }
+
+        self.formatter.write_str(line)?;
+        self.newline = self.formatter.alternate() && line.ends_with('\n');
+        Ok(())
+    }
+}
+
+impl<W> fmt::Write for UfmtWriter<'_, '_, W>
+where
+    W: uWrite + ?Sized,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for line in s.split_inclusive('\n') {
+            if let Err(e) = self.write_line(line) {
+                self.error = Some(e);
+                return Err(fmt::Error);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn write_core<W>(f: &mut Formatter<'_, W>, args: fmt::Arguments<'_>) -> Result<(), W::Error>
+where
+    W: uWrite + ?Sized,
+{
+    let mut writer = UfmtWriter {
+        error: None,
+        formatter: f,
+        newline: false,
+    };
+
+    match fmt::write(&mut writer, args) {
+        Err(fmt::Error) => writer.error.map_or(Ok(()), Err),
+        Ok(()) => Ok(()),
+    }
+}
+
+impl<T> uDisplay for FromCoreFmt<T>
+where
+    T: fmt::Display,
+{
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        if f.sign_plus() {
+            write_core(f, format_args!("{:+}", self.0))
+        } else {
+            write_core(f, format_args!("{}", self.0))
+        }
+    }
+}
+
+impl<T> uDebug for FromCoreFmt<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized,
+    {
+        if f.alternate() {
+            write_core(f, format_args!("{:#?}", self.0))
+        } else {
+            write_core(f, format_args!("{:?}", self.0))
+        }
+    }
+}
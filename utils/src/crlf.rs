@@ -0,0 +1,73 @@
+use ufmt_write::uWrite;
+
+/// A write adapter that translates `\n` line endings into `\r\n`
+///
+/// Newlines that are already preceded by `\r` are left alone, even when the `\r` came in an
+/// earlier `write_str` call. Nothing is buffered: the input is forwarded in slices, with `\r\n`
+/// written in place of each bare `\n`.
+///
+/// ```
+/// use ufmt::{uwrite, uwriteln};
+/// use ufmt_utils::{Crlf, SliceWriter};
+///
+/// let mut buf = [0; 16];
+/// let mut w = Crlf::new(SliceWriter::new(&mut buf));
+///
+/// uwriteln!(w, "a\nb").unwrap();
+/// uwrite!(w, "c\r\n").unwrap();
+/// assert_eq!(w.free().as_str(), "a\r\nb\r\nc\r\n");
+/// ```
+pub struct Crlf<W>
+where
+    W: uWrite,
+{
+    after_cr: bool,
+    writer: W,
+}
+
+impl<W> Crlf<W>
+where
+    W: uWrite,
+{
+    /// Creates a new `Crlf` adapter
+    pub fn new(writer: W) -> Self {
+        Self {
+            after_cr: false,
+            writer,
+        }
+    }
+
+    /// Destroys the adapter and returns the underlying writer
+    pub fn free(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> uWrite for Crlf<W>
+where
+    W: uWrite,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        if s.is_empty() {
+            return Ok(());
+        }
+
+        for (i, line) in s.split('\n').enumerate() {
+            if i != 0 {
+                // the `\r` may have come in a previous call
+                self.writer
+                    .write_str(if self.after_cr { "\n" } else { "\r\n" })?;
+                self.after_cr = false;
+            }
+
+            if !line.is_empty() {
+                self.writer.write_str(line)?;
+                self.after_cr = line.ends_with('\r');
+            }
+        }
+
+        Ok(())
+    }
+}
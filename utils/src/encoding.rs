@@ -0,0 +1,313 @@
+use core::str;
+
+use heapless::Vec;
+use ufmt_write::uWrite;
+
+use crate::{escape::HEX, ByteSink, Overflow};
+
+/// A sink for UTF-16 (or UCS-2) code units
+pub trait Utf16Sink {
+    /// The error associated to this sink
+    type Error;
+
+    /// Writes all the `units` into this sink
+    fn write_units(&mut self, units: &[u16]) -> Result<(), Self::Error>;
+}
+
+impl<S> Utf16Sink for &mut S
+where
+    S: Utf16Sink + ?Sized,
+{
+    type Error = S::Error;
+
+    fn write_units(&mut self, units: &[u16]) -> Result<(), S::Error> {
+        (**self).write_units(units)
+    }
+}
+
+impl<const N: usize> Utf16Sink for Vec<u16, N> {
+    type Error = Overflow;
+
+    fn write_units(&mut self, units: &[u16]) -> Result<(), Overflow> {
+        self.extend_from_slice(units).map_err(|_| Overflow)
+    }
+}
+
+/// What the encoding adapters do with characters that the target encoding can't represent
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Unrepresentable {
+    /// Write this character instead; it's dropped too if it can't be represented either
+    Replace(char),
+    /// Write a `\u{XXXX}` escape, with the code point in hexadecimal
+    Escape,
+    /// Drop the character
+    Skip,
+}
+
+/// Collects code units and forwards them to a sink in batches
+struct Batch<T, F> {
+    buffer: [T; 32],
+    flush: F,
+    len: usize,
+}
+
+impl<T, F, E> Batch<T, F>
+where
+    T: Copy + Default + From<u8>,
+    F: FnMut(&[T]) -> Result<(), E>,
+{
+    fn new(flush: F) -> Self {
+        Self {
+            buffer: [T::default(); 32],
+            flush,
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, unit: T) -> Result<(), E> {
+        if self.len == self.buffer.len() {
+            (self.flush)(&self.buffer)?;
+            self.len = 0;
+        }
+
+        self.buffer[self.len] = unit;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn escape(&mut self, c: char) -> Result<(), E> {
+        for byte in b"\\u{" {
+            self.push((*byte).into())?;
+        }
+
+        let c = u32::from(c);
+        let digits = (32 - (c | 1).leading_zeros()).div_ceil(4);
+        for i in (0..digits).rev() {
+            self.push(HEX[(c >> (4 * i)) as usize & 0xf].into())?;
+        }
+
+        self.push(b'}'.into())
+    }
+
+    fn finish(mut self) -> Result<(), E> {
+        match self.buffer.get(..self.len) {
+            Some(units) if !units.is_empty() => (self.flush)(units),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Encodes `s` one `char` to one unit, applying `policy` to the `char`s that `encode` rejects
+fn encode<T, F, E>(
+    s: &str,
+    policy: Unrepresentable,
+    encode: fn(char) -> Option<T>,
+    flush: F,
+) -> Result<(), E>
+where
+    T: Copy + Default + From<u8>,
+    F: FnMut(&[T]) -> Result<(), E>,
+{
+    let mut batch = Batch::new(flush);
+    for c in s.chars() {
+        match (encode(c), policy) {
+            (Some(unit), _) => batch.push(unit)?,
+            (None, Unrepresentable::Replace(r)) => {
+                if let Some(unit) = encode(r) {
+                    batch.push(unit)?;
+                }
+            }
+            (None, Unrepresentable::Escape) => batch.escape(c)?,
+            (None, Unrepresentable::Skip) => {}
+        }
+    }
+
+    batch.finish()
+}
+
+/// A write adapter that restricts its output to ASCII
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{Ascii, SliceWriter, Unrepresentable};
+///
+/// let mut buf = [0; 32];
+/// let mut w = Ascii::new(SliceWriter::new(&mut buf), Unrepresentable::Escape);
+///
+/// uwrite!(w, "{} μs", 12).unwrap();
+/// assert_eq!(w.free().as_str(), "12 \\u{3BC}s");
+/// ```
+pub struct Ascii<W>
+where
+    W: uWrite,
+{
+    policy: Unrepresentable,
+    writer: W,
+}
+
+impl<W> Ascii<W>
+where
+    W: uWrite,
+{
+    /// Creates a new `Ascii` adapter that handles non-ASCII characters according to `policy`
+    pub fn new(writer: W, policy: Unrepresentable) -> Self {
+        Self { policy, writer }
+    }
+
+    /// Destroys the adapter and returns the underlying writer
+    pub fn free(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> uWrite for Ascii<W>
+where
+    W: uWrite,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        if s.is_ascii() {
+            return self.writer.write_str(s);
+        }
+
+        let writer = &mut self.writer;
+        encode(
+            s,
+            self.policy,
+            |c| if c.is_ascii() { Some(c as u8) } else { None },
+            // NOTE only ASCII is batched so this never falls back to the default
+            |bytes| writer.write_str(str::from_utf8(bytes).unwrap_or_default()),
+        )
+    }
+}
+
+/// A write adapter that encodes its output in Latin-1 (ISO 8859-1)
+///
+/// ```
+/// use heapless::Vec;
+/// use ufmt::uwrite;
+/// use ufmt_utils::{Latin1, Unrepresentable};
+///
+/// let mut w = Latin1::new(Vec::<u8, 16>::new(), Unrepresentable::Replace('?'));
+///
+/// uwrite!(w, "{}°C ±1 ≈", 21).unwrap();
+/// assert_eq!(w.free(), b"21\xb0C \xb11 ?");
+/// ```
+pub struct Latin1<W>
+where
+    W: ByteSink,
+{
+    policy: Unrepresentable,
+    sink: W,
+}
+
+impl<W> Latin1<W>
+where
+    W: ByteSink,
+{
+    /// Creates a new `Latin1` adapter that handles characters above `U+00FF` according to
+    /// `policy`
+    pub fn new(sink: W, policy: Unrepresentable) -> Self {
+        Self { policy, sink }
+    }
+
+    /// Destroys the adapter and returns the underlying sink
+    pub fn free(self) -> W {
+        self.sink
+    }
+}
+
+impl<W> uWrite for Latin1<W>
+where
+    W: ByteSink,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        let sink = &mut self.sink;
+        encode(
+            s,
+            self.policy,
+            |c| u8::try_from(c).ok(),
+            |bytes| sink.write_bytes(bytes),
+        )
+    }
+}
+
+/// A write adapter that encodes its output in UTF-16, or in UCS-2
+///
+/// UCS-2, used for example by the UEFI console, can only represent the Basic Multilingual Plane.
+///
+/// ```
+/// use heapless::Vec;
+/// use ufmt::uwrite;
+/// use ufmt_utils::{Unrepresentable, Utf16};
+///
+/// let mut w = Utf16::new(Vec::<u16, 16>::new());
+/// uwrite!(w, "{}🦀", 1).unwrap();
+/// assert_eq!(w.free(), [0x31, 0xd83e, 0xdd80]);
+///
+/// let mut w = Utf16::ucs2(Vec::<u16, 16>::new(), Unrepresentable::Replace('\u{fffd}'));
+/// uwrite!(w, "{}🦀", 1).unwrap();
+/// assert_eq!(w.free(), [0x31, 0xfffd]);
+/// ```
+pub struct Utf16<W>
+where
+    W: Utf16Sink,
+{
+    policy: Option<Unrepresentable>,
+    sink: W,
+}
+
+impl<W> Utf16<W>
+where
+    W: Utf16Sink,
+{
+    /// Creates a new `Utf16` adapter; characters outside the BMP are written as surrogate pairs
+    pub fn new(sink: W) -> Self {
+        Self { policy: None, sink }
+    }
+
+    /// Creates a new `Utf16` adapter that writes UCS-2; characters outside the BMP are handled
+    /// according to `policy`
+    pub fn ucs2(sink: W, policy: Unrepresentable) -> Self {
+        Self {
+            policy: Some(policy),
+            sink,
+        }
+    }
+
+    /// Destroys the adapter and returns the underlying sink
+    pub fn free(self) -> W {
+        self.sink
+    }
+}
+
+impl<W> uWrite for Utf16<W>
+where
+    W: Utf16Sink,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        let sink = &mut self.sink;
+        if let Some(policy) = self.policy {
+            return encode(
+                s,
+                policy,
+                |c| u16::try_from(u32::from(c)).ok(),
+                |units| sink.write_units(units),
+            );
+        }
+
+        let mut batch = Batch::new(|units: &[u16]| sink.write_units(units));
+        for c in s.chars() {
+            for unit in c.encode_utf16(&mut [0; 2]) {
+                batch.push(*unit)?;
+            }
+        }
+
+        batch.finish()
+    }
+}
@@ -0,0 +1,193 @@
+use ufmt_write::uWrite;
+
+pub(crate) const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Writes `s` into `writer`, replacing the bytes for which `escape` returns a replacement
+///
+/// `escape` may only return `Some` for ASCII bytes or for every byte of a multi-byte `char`, so
+/// the unescaped runs in between always start and end on `char` boundaries.
+fn escape<W>(
+    writer: &mut W,
+    s: &str,
+    escape: for<'a> fn(u8, &'a mut [u8; 6]) -> Option<&'a str>,
+) -> Result<(), W::Error>
+where
+    W: uWrite + ?Sized,
+{
+    let mut buf = [0; 6];
+    let mut start = 0;
+    for (i, byte) in s.bytes().enumerate() {
+        if let Some(escaped) = escape(byte, &mut buf) {
+            if let Some(run) = s.get(start..i) {
+                if !run.is_empty() {
+                    writer.write_str(run)?;
+                }
+            }
+
+            writer.write_str(escaped)?;
+            start = i + 1;
+        }
+    }
+
+    match s.get(start..) {
+        Some(run) if !run.is_empty() => writer.write_str(run),
+        _ => Ok(()),
+    }
+}
+
+/// Writes `prefix` followed by the hexadecimal digits of `byte` into `buf`
+fn hex<'a>(buf: &'a mut [u8; 6], prefix: &str, byte: u8) -> &'a str {
+    let len = prefix.len() + 2;
+    buf[..prefix.len()].copy_from_slice(prefix.as_bytes());
+    buf[len - 2] = HEX[usize::from(byte >> 4)];
+    buf[len - 1] = HEX[usize::from(byte & 0xf)];
+
+    // NOTE only ASCII has been written so this never falls back to the default
+    core::str::from_utf8(&buf[..len]).unwrap_or_default()
+}
+
+/// A write adapter that escapes the contents of a JSON string
+///
+/// `"`, `\` and control characters are escaped; the surrounding quotes are not written.
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{JsonStringEscape, SliceWriter};
+///
+/// let mut buf = [0; 32];
+/// let mut w = SliceWriter::new(&mut buf);
+///
+/// uwrite!(w, "{{\"msg\":\"").unwrap();
+/// uwrite!(JsonStringEscape(&mut w), "{}\n", "\"hi\"").unwrap();
+/// uwrite!(w, "\"}}").unwrap();
+/// assert_eq!(w.as_str(), r#"{"msg":"\"hi\"\n"}"#);
+/// ```
+pub struct JsonStringEscape<W>(pub W)
+where
+    W: uWrite;
+
+impl<W> uWrite for JsonStringEscape<W>
+where
+    W: uWrite,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        escape(&mut self.0, s, |byte, buf| match byte {
+            b'"' => Some("\\\""),
+            b'\\' => Some("\\\\"),
+            b'\n' => Some("\\n"),
+            b'\r' => Some("\\r"),
+            b'\t' => Some("\\t"),
+            0x08 => Some("\\b"),
+            0x0c => Some("\\f"),
+            0..=0x1f => Some(hex(buf, "\\u00", byte)),
+            _ => None,
+        })
+    }
+}
+
+/// A write adapter that escapes the contents of a quoted CSV field
+///
+/// Quotes are doubled; the surrounding quotes are not written, and must be, for fields that may
+/// contain separators or newlines.
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{CsvFieldEscape, SliceWriter};
+///
+/// let mut buf = [0; 32];
+/// let mut w = SliceWriter::new(&mut buf);
+///
+/// uwrite!(w, "1,\"").unwrap();
+/// uwrite!(CsvFieldEscape(&mut w), "{}, {}", "\"a\"", 2).unwrap();
+/// uwrite!(w, "\"").unwrap();
+/// assert_eq!(w.as_str(), r#"1,"""a"", 2""#);
+/// ```
+pub struct CsvFieldEscape<W>(pub W)
+where
+    W: uWrite;
+
+impl<W> uWrite for CsvFieldEscape<W>
+where
+    W: uWrite,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        escape(&mut self.0, s, |byte, _| match byte {
+            b'"' => Some("\"\""),
+            _ => None,
+        })
+    }
+}
+
+/// A write adapter that percent-encodes everything but the unreserved URL characters
+///
+/// `A-Z`, `a-z`, `0-9`, `-`, `.`, `_` and `~` are written as they are; every other byte,
+/// including each byte of non-ASCII characters, is written as `%XX`.
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{PercentEncode, SliceWriter};
+///
+/// let mut buf = [0; 32];
+/// let mut w = PercentEncode(SliceWriter::new(&mut buf));
+///
+/// uwrite!(w, "{} μs&x=1", 12).unwrap();
+/// assert_eq!(w.0.as_str(), "12%20%CE%BCs%26x%3D1");
+/// ```
+pub struct PercentEncode<W>(pub W)
+where
+    W: uWrite;
+
+impl<W> uWrite for PercentEncode<W>
+where
+    W: uWrite,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        escape(&mut self.0, s, |byte, buf| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => None,
+            _ => Some(hex(buf, "%", byte)),
+        })
+    }
+}
+
+/// A write adapter that escapes HTML special characters
+///
+/// `&`, `<`, `>`, `"` and `'` are replaced with character references, so the output is safe in
+/// both text and quoted attribute values.
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{HtmlEscape, SliceWriter};
+///
+/// let mut buf = [0; 32];
+/// let mut w = HtmlEscape(SliceWriter::new(&mut buf));
+///
+/// uwrite!(w, "<b>{}</b>", "&").unwrap();
+/// assert_eq!(w.0.as_str(), "&lt;b&gt;&amp;&lt;/b&gt;");
+/// ```
+pub struct HtmlEscape<W>(pub W)
+where
+    W: uWrite;
+
+impl<W> uWrite for HtmlEscape<W>
+where
+    W: uWrite,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        escape(&mut self.0, s, |byte, _| match byte {
+            b'&' => Some("&amp;"),
+            b'<' => Some("&lt;"),
+            b'>' => Some("&gt;"),
+            b'"' => Some("&quot;"),
+            b'\'' => Some("&#39;"),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,446 @@
+use heapless::Vec;
+use ufmt_write::uWrite;
+
+use crate::ByteSink;
+
+/// Error returned by the [`Cobs`] adapter
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CobsError<E> {
+    /// A run of more than `N` non-zero bytes was written
+    Overflow,
+    /// The sink failed
+    Sink(E),
+}
+
+/// A write adapter that frames its output with Consistent Overhead Byte Stuffing
+///
+/// Each packet ends with a `0x00` delimiter, which appears nowhere else in the output. Up to 254
+/// bytes are held back until it's known whether a zero byte follows them; `N` is the size of that
+/// buffer, so with `N < 254` writing longer runs of non-zero bytes fails with
+/// [`CobsError::Overflow`].
+///
+/// The end of a `uwrite!` call can't be observed by the adapter, so packets are ended with
+/// [`finish`](Self::finish), or by writing them within [`frame`](Self::frame).
+///
+/// After an overflow the rest of the packet is discarded: writes fail until the packet is ended,
+/// and [`finish`](Self::finish) then ends it in a way that decoders reject as malformed.
+///
+/// ```
+/// use heapless::Vec;
+/// use ufmt::uwrite;
+/// use ufmt_utils::{Cobs, CobsDecoder};
+///
+/// let mut w = Cobs::<_, 254>::new(Vec::<u8, 32>::new());
+/// w.frame(|w| uwrite!(w, "x={}", 1)).unwrap();
+/// w.frame(|w| uwrite!(w, "y")).unwrap();
+///
+/// let encoded = w.free();
+/// assert_eq!(encoded, b"\x04x=1\x00\x02y\x00");
+///
+/// let mut decoder = CobsDecoder::<16>::new();
+/// let mut frames = 0;
+/// for byte in &encoded {
+///     if let Some(frame) = decoder.feed(*byte).unwrap() {
+///         assert_eq!(frame, [&b"x=1"[..], b"y"][frames]);
+///         frames += 1;
+///     }
+/// }
+/// assert_eq!(frames, 2);
+/// ```
+pub struct Cobs<W, const N: usize>
+where
+    W: ByteSink,
+{
+    after_full: bool,
+    overflowed: bool,
+    run: Vec<u8, N>,
+    sink: W,
+    // whether part of the current packet has been written into the sink
+    started: bool,
+}
+
+impl<W, const N: usize> Cobs<W, N>
+where
+    W: ByteSink,
+{
+    /// Creates a new `Cobs` adapter
+    pub fn new(sink: W) -> Self {
+        Self {
+            after_full: false,
+            overflowed: false,
+            run: Vec::new(),
+            sink,
+            started: false,
+        }
+    }
+
+    /// Ends the current packet
+    ///
+    /// Returns [`CobsError::Overflow`] if the packet has been discarded due to an overflow.
+    pub fn finish(&mut self) -> Result<(), CobsError<W::Error>> {
+        let after_full = self.after_full;
+        let overflowed = self.overflowed;
+        let started = self.started;
+        self.after_full = false;
+        self.overflowed = false;
+        self.started = false;
+
+        if overflowed {
+            if started {
+                // a code byte that promises a byte that never comes
+                self.sink.write_bytes(&[2, 0]).map_err(CobsError::Sink)?;
+            }
+
+            return Err(CobsError::Overflow);
+        }
+
+        if !(self.run.is_empty() && after_full) {
+            self.emit_run()?;
+            self.started = false;
+        }
+
+        self.sink.write_bytes(&[0]).map_err(CobsError::Sink)
+    }
+
+    /// Writes a packet with `f` and then ends it, even if `f` fails
+    pub fn frame<F>(&mut self, f: F) -> Result<(), CobsError<W::Error>>
+    where
+        F: FnOnce(&mut Self) -> Result<(), CobsError<W::Error>>,
+    {
+        let result = f(self);
+        let finished = self.finish();
+        result.and(finished)
+    }
+
+    /// Destroys the adapter and returns the underlying sink
+    ///
+    /// An unfinished packet is discarded.
+    pub fn free(self) -> W {
+        self.sink
+    }
+
+    fn emit_run(&mut self) -> Result<(), CobsError<W::Error>> {
+        // NOTE the run never holds more than 254 bytes
+        let code = self.run.len() as u8 + 1;
+        self.sink.write_bytes(&[code]).map_err(CobsError::Sink)?;
+        self.sink.write_bytes(&self.run).map_err(CobsError::Sink)?;
+        self.run.clear();
+        self.started = true;
+        Ok(())
+    }
+}
+
+impl<W, const N: usize> uWrite for Cobs<W, N>
+where
+    W: ByteSink,
+{
+    type Error = CobsError<W::Error>;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        if self.overflowed {
+            return Err(CobsError::Overflow);
+        }
+
+        for byte in s.bytes() {
+            self.after_full = false;
+
+            if byte == 0 {
+                self.emit_run()?;
+                continue;
+            }
+
+            if self.run.push(byte).is_err() {
+                self.overflowed = true;
+                self.run.clear();
+                return Err(CobsError::Overflow);
+            }
+            if self.run.len() == 254 {
+                self.emit_run()?;
+                self.after_full = true;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+const END: u8 = 0xc0;
+const ESC: u8 = 0xdb;
+const ESC_END: u8 = 0xdc;
+const ESC_ESC: u8 = 0xdd;
+
+/// A write adapter that frames its output with the Serial Line Internet Protocol (RFC 1055)
+///
+/// Each packet ends with an `0xC0` delimiter; nothing is buffered. Packets are ended with
+/// [`finish`](Self::finish), or by writing them within [`frame`](Self::frame).
+///
+/// ```
+/// use heapless::Vec;
+/// use ufmt::uwrite;
+/// use ufmt_utils::{Slip, SlipDecoder};
+///
+/// let mut w = Slip::new(Vec::<u8, 32>::new());
+/// w.frame(|w| uwrite!(w, "ۀ={}", 1)).unwrap();
+///
+/// let encoded = w.free();
+/// assert_eq!(encoded, b"\xdb\xdd\x80=1\xc0");
+///
+/// let mut decoder = SlipDecoder::<16>::new();
+/// let (last, rest) = encoded.split_last().unwrap();
+/// for byte in rest {
+///     assert_eq!(decoder.feed(*byte), Ok(None));
+/// }
+/// assert_eq!(decoder.feed(*last), Ok(Some("ۀ=1".as_bytes())));
+/// ```
+pub struct Slip<W>
+where
+    W: ByteSink,
+{
+    sink: W,
+}
+
+impl<W> Slip<W>
+where
+    W: ByteSink,
+{
+    /// Creates a new `Slip` adapter
+    pub fn new(sink: W) -> Self {
+        Self { sink }
+    }
+
+    /// Ends the current packet
+    pub fn finish(&mut self) -> Result<(), W::Error> {
+        self.sink.write_bytes(&[END])
+    }
+
+    /// Writes a packet with `f` and then ends it
+    pub fn frame<F>(&mut self, f: F) -> Result<(), W::Error>
+    where
+        F: FnOnce(&mut Self) -> Result<(), W::Error>,
+    {
+        f(self)?;
+        self.finish()
+    }
+
+    /// Destroys the adapter and returns the underlying sink
+    pub fn free(self) -> W {
+        self.sink
+    }
+}
+
+impl<W> uWrite for Slip<W>
+where
+    W: ByteSink,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        let bytes = s.as_bytes();
+        let mut start = 0;
+        for (i, byte) in bytes.iter().enumerate() {
+            let escaped = match *byte {
+                END => [ESC, ESC_END],
+                ESC => [ESC, ESC_ESC],
+                _ => continue,
+            };
+
+            self.sink.write_bytes(&bytes[start..i])?;
+            self.sink.write_bytes(&escaped)?;
+            start = i + 1;
+        }
+
+        self.sink.write_bytes(&bytes[start..])
+    }
+}
+
+/// Error returned by the [`CobsDecoder`] and [`SlipDecoder`]
+///
+/// The packet being decoded is dropped; decoding resumes with the next one.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// The packet doesn't fit in the decoder's buffer
+    Overflow,
+    /// The packet is not properly encoded
+    Malformed,
+}
+
+/// A decoder for packets framed by [`Cobs`]
+///
+/// `N` is the maximum size of a decoded packet.
+pub struct CobsDecoder<const N: usize> {
+    buffer: Vec<u8, N>,
+    code: u8,
+    done: bool,
+    error: bool,
+    remaining: u8,
+    started: bool,
+    zero: bool,
+}
+
+impl<const N: usize> CobsDecoder<N> {
+    /// Creates a new decoder
+    pub const fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            code: 0,
+            done: false,
+            error: false,
+            remaining: 0,
+            started: false,
+            zero: false,
+        }
+    }
+
+    /// Feeds the next byte; returns the decoded packet once its delimiter has been fed
+    ///
+    /// Empty packets are skipped.
+    pub fn feed(&mut self, byte: u8) -> Result<Option<&[u8]>, DecodeError> {
+        if self.done {
+            self.buffer.clear();
+            self.done = false;
+        }
+
+        if byte == 0 {
+            let (error, started, remaining) = (self.error, self.started, self.remaining);
+            self.reset();
+
+            return if error || !started {
+                Ok(None)
+            } else if remaining != 0 {
+                self.buffer.clear();
+                Err(DecodeError::Malformed)
+            } else if self.buffer.is_empty() {
+                Ok(None)
+            } else {
+                self.done = true;
+                Ok(Some(&self.buffer))
+            };
+        }
+
+        if self.error {
+            return Ok(None);
+        }
+
+        let pushed = if self.remaining == 0 {
+            // code byte
+            self.started = true;
+            self.code = byte;
+            self.remaining = byte - 1;
+            let pushed = if self.zero {
+                self.buffer.push(0)
+            } else {
+                Ok(())
+            };
+            self.zero = false;
+            pushed
+        } else {
+            self.remaining -= 1;
+            self.buffer.push(byte)
+        };
+
+        if pushed.is_err() {
+            self.buffer.clear();
+            self.error = true;
+            return Err(DecodeError::Overflow);
+        }
+
+        if self.remaining == 0 {
+            self.zero = self.code != 0xff;
+        }
+
+        Ok(None)
+    }
+
+    fn reset(&mut self) {
+        self.code = 0;
+        self.error = false;
+        self.remaining = 0;
+        self.started = false;
+        self.zero = false;
+    }
+}
+
+impl<const N: usize> Default for CobsDecoder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A decoder for packets framed by [`Slip`]
+///
+/// `N` is the maximum size of a decoded packet.
+pub struct SlipDecoder<const N: usize> {
+    buffer: Vec<u8, N>,
+    done: bool,
+    error: bool,
+    escaped: bool,
+}
+
+impl<const N: usize> SlipDecoder<N> {
+    /// Creates a new decoder
+    pub const fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            done: false,
+            error: false,
+            escaped: false,
+        }
+    }
+
+    /// Feeds the next byte; returns the decoded packet once its delimiter has been fed
+    ///
+    /// Empty packets are skipped.
+    pub fn feed(&mut self, byte: u8) -> Result<Option<&[u8]>, DecodeError> {
+        if self.done {
+            self.buffer.clear();
+            self.done = false;
+        }
+
+        if byte == END {
+            let error = self.error;
+            self.error = false;
+            self.escaped = false;
+
+            return if error || self.buffer.is_empty() {
+                Ok(None)
+            } else {
+                self.done = true;
+                Ok(Some(&self.buffer))
+            };
+        }
+
+        if self.error {
+            return Ok(None);
+        }
+
+        let byte = match (self.escaped, byte) {
+            (false, ESC) => {
+                self.escaped = true;
+                return Ok(None);
+            }
+            (false, byte) => byte,
+            (true, ESC_END) => END,
+            (true, ESC_ESC) => ESC,
+            (true, _) => {
+                self.buffer.clear();
+                self.error = true;
+                return Err(DecodeError::Malformed);
+            }
+        };
+        self.escaped = false;
+
+        if self.buffer.push(byte).is_err() {
+            self.buffer.clear();
+            self.error = true;
+            return Err(DecodeError::Overflow);
+        }
+
+        Ok(None)
+    }
+}
+
+impl<const N: usize> Default for SlipDecoder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
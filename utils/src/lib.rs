@@ -2,7 +2,7 @@
 //!
 //! # Minimum Supported Rust Version (MSRV)
 //!
-//! This crate is guaranteed to compile on stable Rust 1.36 and up. It *might* compile on older
+//! This crate is guaranteed to compile on stable Rust 1.75 and up. It *might* compile on older
 //! versions but that may change in any new patch release.
 
 #![deny(missing_docs)]
@@ -13,9 +13,54 @@
 
 use core::{convert::Infallible, fmt, str};
 
-pub use heapless::consts;
-use heapless::{ArrayLength, String};
-use ufmt_write::uWrite;
+#[cfg(feature = "core-fmt")]
+pub use crate::core_fmt::{AsCoreFmt, FromCoreFmt};
+#[cfg(target_has_atomic = "ptr")]
+pub use crate::ring::{Consumer, LogRing, OverflowPolicy, Producer};
+#[cfg(feature = "embedded-io")]
+pub use crate::serial::IoWriteAdapter;
+#[cfg(feature = "embedded-hal-nb")]
+pub use crate::serial::SerialWriteAdapter;
+#[cfg(feature = "critical-section")]
+pub use crate::shared::{
+    set_global_writer, unstable_with_global_writer, SharedWriter, UnstableGlobalWriter,
+};
+pub use crate::{
+    checksum::{Checksum, ChecksumAlgorithm, Crc16, Crc32, Crc8, HashWriter, Xor8},
+    combinators::{Fallback, MapErr, Take, Tee, TeeError},
+    crlf::Crlf,
+    encoding::{Ascii, Latin1, Unrepresentable, Utf16, Utf16Sink},
+    escape::{CsvFieldEscape, HtmlEscape, JsonStringEscape, PercentEncode},
+    framing::{Cobs, CobsDecoder, CobsError, DecodeError, Slip, SlipDecoder},
+    prefixed::{DisplayPrefix, Prefix, Prefixed},
+    sink::ByteSink,
+    wrap::Wrap,
+};
+use heapless::String;
+pub use ufmt::LengthCounter;
+use ufmt_write::{uWrite, uWriteCheckpoint};
+
+// Implementation detail of the `uformat_buf!`, `uprint!` and `uprintln!` macros
+#[doc(hidden)]
+pub use ufmt;
+
+mod checksum;
+mod combinators;
+#[cfg(feature = "core-fmt")]
+mod core_fmt;
+mod crlf;
+mod encoding;
+mod escape;
+mod framing;
+mod prefixed;
+#[cfg(target_has_atomic = "ptr")]
+mod ring;
+#[cfg(any(feature = "embedded-hal-nb", feature = "embedded-io"))]
+mod serial;
+#[cfg(feature = "critical-section")]
+mod shared;
+mod sink;
+mod wrap;
 
 /// A write adapter that ignores all errors
 pub struct Ignore<W>
@@ -52,74 +97,235 @@ where
     }
 }
 
+/// When a [`LineBuffered`] adapter flushes its buffer, besides when the buffer is full
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FlushPolicy {
+    /// After every newline (`\n`)
+    Line,
+    /// After every carriage return (`\r`)
+    CarriageReturn,
+    /// Only when the buffer is full, on [`LineBuffered::flush`] and on [`LineBuffered::free`]
+    Full,
+    /// At the end of every `write_str` call
+    EveryWrite,
+}
+
 /// A write adapter that buffers writes and automatically flushes on newlines
-pub struct LineBuffered<W, N>
+///
+/// `N` is the capacity of the buffer in bytes. Other flush policies can be selected with
+/// [`LineBuffered::with_policy`]. Whatever is left in the buffer is flushed when the adapter is
+/// freed; errors are ignored at that point so call [`LineBuffered::flush`] beforehand if they
+/// matter. The adapter doesn't flush on drop, so that `free` can hand back the writer without a
+/// panicking branch; wrap it in a [`FlushOnDrop`] adapter for that.
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{FlushPolicy, LineBuffered, SliceWriter};
+///
+/// let mut buf = [0; 16];
+/// let mut w = LineBuffered::<_, 8>::with_policy(SliceWriter::new(&mut buf), FlushPolicy::Full);
+///
+/// uwrite!(w, "{}\n{}", 1, 2).unwrap();
+/// assert_eq!(w.free().as_str(), "1\n2");
+/// ```
+pub struct LineBuffered<W, const N: usize>
 where
-    N: ArrayLength<u8>,
     W: uWrite,
 {
     buffer: String<N>,
+    flushes: usize,
+    policy: FlushPolicy,
     writer: W,
 }
 
-impl<W, N> LineBuffered<W, N>
+impl<W, const N: usize> LineBuffered<W, N>
 where
-    N: ArrayLength<u8>,
     W: uWrite,
 {
-    /// Creates a new `LineBuffered` adapter
+    /// Creates a new `LineBuffered` adapter that flushes on newlines
     pub fn new(writer: W) -> Self {
+        Self::with_policy(writer, FlushPolicy::Line)
+    }
+
+    /// Creates a new `LineBuffered` adapter with the given flush policy
+    pub fn with_policy(writer: W, policy: FlushPolicy) -> Self {
         Self {
             buffer: String::new(),
+            flushes: 0,
+            policy,
             writer,
         }
     }
 
     /// Flushes the contents of the buffer
     pub fn flush(&mut self) -> Result<(), W::Error> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
         let ret = self.writer.write_str(&self.buffer);
         self.buffer.clear();
+        self.flushes = self.flushes.wrapping_add(1);
         ret
     }
 
-    /// Destroys the adapter and returns the underlying writer
-    pub fn free(self) -> W {
+    /// Flushes the buffer, ignoring errors, then destroys the adapter and returns the underlying
+    /// writer
+    pub fn free(mut self) -> W {
+        self.flush().ok();
         self.writer
     }
 
     fn push_str(&mut self, s: &str) -> Result<(), W::Error> {
-        let len = s.as_bytes().len();
-        if self.buffer.len() + len > self.buffer.capacity() {
+        if self.buffer.push_str(s).is_err() {
             self.flush()?;
+
+            if self.buffer.push_str(s).is_err() {
+                // `s` doesn't fit even in the empty buffer
+                self.writer.write_str(s)?;
+            }
         }
 
-        if len > self.buffer.capacity() {
-            self.writer.write_str(s)?;
-        } else {
-            unsafe { self.buffer.push_str(s).unwrap_unchecked() }
+        Ok(())
+    }
+
+    fn push_lines(&mut self, s: &str, terminator: char) -> Result<(), W::Error> {
+        for line in s.split_inclusive(terminator) {
+            self.push_str(line)?;
+            if line.ends_with(terminator) {
+                self.flush()?;
+            }
         }
 
         Ok(())
     }
 }
 
-impl<W, N> uWrite for LineBuffered<W, N>
+impl<W, const N: usize> uWrite for LineBuffered<W, N>
 where
-    N: ArrayLength<u8>,
     W: uWrite,
 {
     type Error = W::Error;
 
     #[inline]
     fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
-        for line in s.split_inclusive('\n') {
-            self.push_str(line)?;
-            if line.ends_with('\n') {
-                self.flush()?;
+        match self.policy {
+            FlushPolicy::Line => self.push_lines(s, '\n'),
+            FlushPolicy::CarriageReturn => self.push_lines(s, '\r'),
+            FlushPolicy::Full => self.push_str(s),
+            FlushPolicy::EveryWrite => {
+                self.push_str(s)?;
+                self.flush()
             }
         }
+    }
+}
 
-        Ok(())
+impl<W, const N: usize> Flush for LineBuffered<W, N>
+where
+    W: uWrite,
+{
+    fn flush(&mut self) -> Result<(), W::Error> {
+        LineBuffered::flush(self)
+    }
+}
+
+/// A write adapter that holds data back until it's flushed
+pub trait Flush: uWrite {
+    /// Writes out the data that's being held back
+    fn flush(&mut self) -> Result<(), Self::Error>;
+}
+
+/// A write adapter that flushes the wrapped adapter when dropped, ignoring errors
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{FlushOnDrop, LineBuffered};
+///
+/// let mut s = String::new();
+/// {
+///     let mut w = FlushOnDrop::new(LineBuffered::<_, 8>::new(&mut s));
+///     uwrite!(w, "no newline").unwrap();
+/// }
+/// assert_eq!(s, "no newline");
+/// ```
+pub struct FlushOnDrop<W>
+where
+    W: Flush,
+{
+    writer: W,
+}
+
+impl<W> FlushOnDrop<W>
+where
+    W: Flush,
+{
+    /// Creates a new `FlushOnDrop` adapter
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Returns a mutable reference to the wrapped adapter
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+}
+
+impl<W> Drop for FlushOnDrop<W>
+where
+    W: Flush,
+{
+    fn drop(&mut self) {
+        self.writer.flush().ok();
+    }
+}
+
+impl<W> uWrite for FlushOnDrop<W>
+where
+    W: Flush,
+{
+    type Error = W::Error;
+
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        self.writer.write_str(s)
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> Result<(), W::Error> {
+        self.writer.write_char(c)
+    }
+}
+
+/// A snapshot of the state of a [`LineBuffered`] adapter
+pub struct Checkpoint {
+    flushes: usize,
+    len: usize,
+}
+
+impl<W, const N: usize> uWriteCheckpoint for LineBuffered<W, N>
+where
+    W: uWrite,
+{
+    type Checkpoint = Checkpoint;
+
+    fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            flushes: self.flushes,
+            len: self.buffer.len(),
+        }
+    }
+
+    /// Discards the buffered data written since `checkpoint`
+    ///
+    /// If the buffer has been flushed since then, the flushed data can't be taken back; only the
+    /// data buffered after the last flush is discarded.
+    fn rollback(&mut self, checkpoint: Checkpoint) {
+        if checkpoint.flushes == self.flushes {
+            self.buffer.truncate(checkpoint.len);
+        } else {
+            self.buffer.clear();
+        }
     }
 }
 
@@ -155,3 +361,130 @@ where
         self.0.write_str(s)
     }
 }
+
+/// Error returned by [`SliceWriter`], and other fixed-size buffers, when the buffer is too small to
+/// hold the written data
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Overflow;
+
+/// A writer that formats into a byte slice, e.g. a buffer allocated on the stack
+///
+/// A write that doesn't fit in the remaining space fails with [`Overflow`] and leaves the buffer
+/// untouched; the contents written so far remain available through [`SliceWriter::as_str`].
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{Overflow, SliceWriter};
+///
+/// let mut buf = [0; 16];
+/// let mut w = SliceWriter::new(&mut buf);
+///
+/// uwrite!(w, "The answer is {}", 42).unwrap();
+/// assert_eq!(w.as_str(), "The answer is 42");
+/// assert_eq!(w.remaining(), 0);
+///
+/// assert_eq!(uwrite!(w, "!"), Err(Overflow));
+/// assert_eq!(w.into_str(), "The answer is 42");
+/// ```
+///
+/// Combined with `uwrite_atomic!` a message is either written in full or not at all:
+///
+/// ```
+/// use ufmt::{uwrite, uwrite_atomic};
+/// use ufmt_utils::{Overflow, SliceWriter};
+///
+/// let mut buf = [0; 8];
+/// let mut w = SliceWriter::new(&mut buf);
+///
+/// uwrite!(w, "x={} ", 1).unwrap();
+/// assert_eq!(uwrite_atomic!(w, "y={} z={}", 2, 3), Err(Overflow));
+/// assert_eq!(w.as_str(), "x=1 ");
+/// ```
+pub struct SliceWriter<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    /// Creates a new `SliceWriter` that writes into `buffer`, starting at its beginning
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self { buffer, len: 0 }
+    }
+
+    /// Returns the data written so far
+    pub fn as_str(&self) -> &str {
+        // NOTE `buffer[..len]` is always made of whole `str`-s
+        unsafe { str::from_utf8_unchecked(self.buffer.get_unchecked(..self.len)) }
+    }
+
+    /// Destroys the writer and returns the data written so far
+    pub fn into_str(self) -> &'a str {
+        // NOTE `buffer[..len]` is always made of whole `str`-s
+        unsafe { str::from_utf8_unchecked(self.buffer.get_unchecked(..self.len)) }
+    }
+
+    /// Returns the number of bytes that can still be written
+    pub fn remaining(&self) -> usize {
+        self.buffer.len() - self.len
+    }
+}
+
+impl uWrite for SliceWriter<'_> {
+    type Error = Overflow;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Overflow> {
+        let end = self.len + s.len();
+        let dst = self.buffer.get_mut(self.len..end).ok_or(Overflow)?;
+        dst.copy_from_slice(s.as_bytes());
+        self.len = end;
+
+        Ok(())
+    }
+}
+
+impl uWriteCheckpoint for SliceWriter<'_> {
+    type Checkpoint = usize;
+
+    fn checkpoint(&self) -> usize {
+        self.len
+    }
+
+    /// Discards the data written since `len`
+    ///
+    /// A `len` that is past the end of the data written so far, or that doesn't fall on a `char`
+    /// boundary of it, is ignored.
+    fn rollback(&mut self, len: usize) {
+        if len < self.len && self.as_str().is_char_boundary(len) {
+            self.len = len;
+        }
+    }
+}
+
+/// Formats data into a byte buffer and returns the resulting string slice
+///
+/// Shorthand for writing into a [`SliceWriter`] with `ufmt::uwrite!`. Returns
+/// `Result<&str, Overflow>`.
+///
+/// ```
+/// use ufmt_utils::{uformat_buf, Overflow};
+///
+/// let mut buf = [0; 32];
+/// assert_eq!(uformat_buf!(&mut buf, "{} + {} = {}", 1, 2, 3), Ok("1 + 2 = 3"));
+///
+/// let mut buf = [0; 4];
+/// assert_eq!(uformat_buf!(&mut buf, "{}", 12345), Err(Overflow));
+/// ```
+#[macro_export]
+macro_rules! uformat_buf {
+    // IMPORTANT use `tt` fragments instead of `expr` fragments (i.e. `$($exprs:expr),*`)
+    ($buffer:expr, $($tt:tt)*) => {{
+        // the expansion of `uwrite!` refers to the `ufmt` crate, which the caller may not depend on
+        use $crate::ufmt;
+
+        let mut w = $crate::SliceWriter::new($buffer);
+        match ufmt::uwrite!(&mut w, $($tt)*) {
+            Ok(()) => Ok(w.into_str()),
+            Err(e) => Err(e),
+        }
+    }};
+}
@@ -0,0 +1,121 @@
+use ufmt::uDisplay;
+use ufmt_write::uWrite;
+
+/// A prefix that the [`Prefixed`] adapter writes at the start of every line
+///
+/// This is implemented for closures that write into the underlying writer and for
+/// [`DisplayPrefix`].
+pub trait Prefix<W>
+where
+    W: uWrite + ?Sized,
+{
+    /// Writes the prefix into `writer`
+    fn write_prefix(&mut self, writer: &mut W) -> Result<(), W::Error>;
+}
+
+impl<W, F> Prefix<W> for F
+where
+    W: uWrite + ?Sized,
+    F: FnMut(&mut W) -> Result<(), W::Error>,
+{
+    fn write_prefix(&mut self, writer: &mut W) -> Result<(), W::Error> {
+        self(writer)
+    }
+}
+
+/// A [`Prefix`] that formats a `uDisplay` value
+pub struct DisplayPrefix<T>(pub T);
+
+impl<W, T> Prefix<W> for DisplayPrefix<T>
+where
+    W: uWrite + ?Sized,
+    T: uDisplay,
+{
+    fn write_prefix(&mut self, writer: &mut W) -> Result<(), W::Error> {
+        ufmt::uwrite!(writer, "{}", self.0)
+    }
+}
+
+/// A write adapter that writes a prefix at the start of every line
+///
+/// Line starts are tracked across `write_str` calls. The prefix is written lazily, right before
+/// the first character of a line, so a trailing newline doesn't leave a dangling prefix behind.
+///
+/// ```
+/// use ufmt::{uwrite, uwriteln};
+/// use ufmt_utils::{DisplayPrefix, Prefixed, SliceWriter};
+///
+/// let mut buf = [0; 64];
+/// let mut w = Prefixed::new(SliceWriter::new(&mut buf), DisplayPrefix("[net] "));
+///
+/// uwriteln!(w, "up").unwrap();
+/// uwrite!(w, "rx: {}\ntx: {}\n", 1, 2).unwrap();
+/// assert_eq!(w.free().as_str(), "[net] up\n[net] rx: 1\n[net] tx: 2\n");
+/// ```
+///
+/// A closure can compute the prefix on every line
+///
+/// ```
+/// use ufmt::{uwrite, uwriteln};
+/// use ufmt_utils::{Prefixed, SliceWriter};
+///
+/// let mut tick = 0;
+/// let mut buf = [0; 32];
+/// let mut w = Prefixed::new(SliceWriter::new(&mut buf), |w: &mut SliceWriter<'_>| {
+///     tick += 1;
+///     uwrite!(w, "[{}] ", tick)
+/// });
+///
+/// uwriteln!(w, "a\nb").unwrap();
+/// assert_eq!(w.free().as_str(), "[1] a\n[2] b\n");
+/// ```
+pub struct Prefixed<W, P>
+where
+    W: uWrite,
+    P: Prefix<W>,
+{
+    line_start: bool,
+    prefix: P,
+    writer: W,
+}
+
+impl<W, P> Prefixed<W, P>
+where
+    W: uWrite,
+    P: Prefix<W>,
+{
+    /// Creates a new `Prefixed` adapter; the first write starts a new line
+    pub fn new(writer: W, prefix: P) -> Self {
+        Self {
+            line_start: true,
+            prefix,
+            writer,
+        }
+    }
+
+    /// Destroys the adapter and returns the underlying writer
+    pub fn free(self) -> W {
+        self.writer
+    }
+}
+
+impl<W, P> uWrite for Prefixed<W, P>
+where
+    W: uWrite,
+    P: Prefix<W>,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        for line in s.split_inclusive('\n') {
+            if self.line_start {
+                self.prefix.write_prefix(&mut self.writer)?;
+            }
+
+            self.writer.write_str(line)?;
+            self.line_start = line.ends_with('\n');
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,345 @@
+use core::{
+    cell::UnsafeCell,
+    ptr, slice,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use ufmt_write::uWrite;
+
+use crate::Overflow;
+
+/// Set in `read` while the consumer is going through the oldest message
+const CLAIMED: usize = 1 << (usize::BITS - 1);
+
+/// Size of the length header that precedes every message
+const HEADER: usize = 2;
+
+/// What a [`LogRing`] does when a message doesn't fit in the free space
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OverflowPolicy {
+    /// Drop the message being written
+    DropNewest,
+    /// Drop the oldest messages to make room; messages that the consumer has started reading
+    /// are never dropped, in that case the message being written is dropped instead. A message
+    /// that's larger than the whole buffer is dropped without touching the old ones
+    OverwriteOldest,
+}
+
+/// A lock-free, single-producer single-consumer ring buffer of formatted messages
+///
+/// The [`Producer`] half implements `uWrite`; what's written to it becomes visible to the
+/// [`Consumer`] half as a whole message once committed. Messages are stored with a 2-byte length
+/// header so they're kept whole when the oldest ones are overwritten; a message can take at most
+/// `N - 2` (and at most 65535) bytes.
+///
+/// Each half can live in a different execution context, for example the producer in an interrupt
+/// handler and the consumer in the main loop. This needs compare-and-swap atomics, so it's not
+/// available on targets like `thumbv6m-none-eabi`.
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{LogRing, OverflowPolicy};
+///
+/// let mut ring = LogRing::<16>::new(OverflowPolicy::OverwriteOldest);
+/// let (mut producer, mut consumer) = ring.split();
+///
+/// producer.message(|w| uwrite!(w, "a={}", 1)).unwrap();
+/// producer.message(|w| uwrite!(w, "b={}", 2)).unwrap();
+/// // evicts the first message
+/// producer.message(|w| uwrite!(w, "c={}", 345)).unwrap();
+/// assert_eq!(producer.dropped(), 1);
+///
+/// let mut out = Vec::new();
+/// while let Some(chunk) = consumer.read() {
+///     out.extend_from_slice(chunk);
+///     consumer.release();
+/// }
+/// assert_eq!(out, b"b=2c=345");
+/// ```
+pub struct LogRing<const N: usize> {
+    buffer: UnsafeCell<[u8; N]>,
+    policy: OverflowPolicy,
+    // positions are kept in the range `0..2 * N` to tell a full buffer from an empty one
+    read: AtomicUsize,
+    write: AtomicUsize,
+}
+
+// NOTE the producer only writes to the free space and the consumer only reads the committed
+// messages; ownership of the bytes changes hands through `read` and `write`
+unsafe impl<const N: usize> Sync for LogRing<N> {}
+
+impl<const N: usize> LogRing<N> {
+    const CHECK: () = assert!(N > HEADER && N < CLAIMED / 2);
+
+    /// Creates an empty ring buffer
+    pub const fn new(policy: OverflowPolicy) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::CHECK;
+
+        Self {
+            buffer: UnsafeCell::new([0; N]),
+            policy,
+            read: AtomicUsize::new(0),
+            write: AtomicUsize::new(0),
+        }
+    }
+
+    /// Splits the ring buffer into its producer and consumer halves
+    pub fn split(&mut self) -> (Producer<'_, N>, Consumer<'_, N>) {
+        // a consumer dropped halfway through a message will have left it claimed
+        *self.read.get_mut() &= !CLAIMED;
+
+        let write = *self.write.get_mut();
+        let this = &*self;
+        (
+            Producer {
+                dropped: 0,
+                failed: false,
+                pending: 0,
+                ring: this,
+                write,
+            },
+            Consumer {
+                chunk: 0,
+                len: 0,
+                offset: 0,
+                reading: false,
+                ring: this,
+                start: 0,
+            },
+        )
+    }
+
+    fn wrap(pos: usize) -> usize {
+        if pos >= 2 * N {
+            pos - 2 * N
+        } else {
+            pos
+        }
+    }
+
+    fn used(read: usize, write: usize) -> usize {
+        Self::wrap(write + 2 * N - (read & !CLAIMED))
+    }
+
+    fn ptr(&self, pos: usize) -> *mut u8 {
+        let index = if pos >= N { pos - N } else { pos };
+        // NOTE `index < N`
+        unsafe { (self.buffer.get() as *mut u8).add(index) }
+    }
+
+    /// Copies `bytes` into the buffer starting at `pos`, wrapping around its end
+    ///
+    /// # Safety
+    ///
+    /// The range must be owned by the caller
+    unsafe fn copy_in(&self, pos: usize, bytes: &[u8]) {
+        let index = if pos >= N { pos - N } else { pos };
+        let first = bytes.len().min(N - index);
+        ptr::copy_nonoverlapping(bytes.as_ptr(), self.ptr(pos), first);
+        ptr::copy_nonoverlapping(bytes.as_ptr().add(first), self.ptr(0), bytes.len() - first);
+    }
+
+    /// Reads the length header of the message at `pos`
+    ///
+    /// # Safety
+    ///
+    /// There must be a committed message at `pos`
+    unsafe fn header(&self, pos: usize) -> usize {
+        let lo = *self.ptr(pos);
+        let hi = *self.ptr(Self::wrap(pos + 1));
+        usize::from(u16::from_le_bytes([lo, hi]))
+    }
+}
+
+/// The writing half of a [`LogRing`]
+///
+/// Writes are accumulated into a pending message, which the consumer can't see until it's
+/// [committed](Self::commit). If the pending message doesn't fit, the writes fail with
+/// [`Overflow`] and the message is dropped when committed.
+pub struct Producer<'a, const N: usize> {
+    dropped: usize,
+    failed: bool,
+    pending: usize,
+    ring: &'a LogRing<N>,
+    write: usize,
+}
+
+impl<const N: usize> Producer<'_, N> {
+    /// Writes a message with `f`; the message is committed if `f` succeeds and discarded
+    /// otherwise
+    pub fn message<F>(&mut self, f: F) -> Result<(), Overflow>
+    where
+        F: FnOnce(&mut Self) -> Result<(), Overflow>,
+    {
+        match f(self) {
+            Ok(()) => self.commit(),
+            Err(e) => {
+                self.abort();
+                Err(e)
+            }
+        }
+    }
+
+    /// Makes the pending message visible to the consumer
+    ///
+    /// Fails, dropping the message, if part of it didn't fit.
+    pub fn commit(&mut self) -> Result<(), Overflow> {
+        if self.failed {
+            self.abort();
+            return Err(Overflow);
+        }
+
+        if self.pending != 0 {
+            let header = (self.pending as u16).to_le_bytes();
+            // NOTE the space was reserved by `write_str`
+            unsafe { self.ring.copy_in(self.write, &header) };
+
+            self.write = LogRing::<N>::wrap(self.write + HEADER + self.pending);
+            self.ring.write.store(self.write, Ordering::Release);
+            self.pending = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Discards the pending message
+    pub fn abort(&mut self) {
+        if self.failed || self.pending != 0 {
+            self.dropped = self.dropped.wrapping_add(1);
+        }
+
+        self.failed = false;
+        self.pending = 0;
+    }
+
+    /// Returns the number of messages that have been dropped, both new and old ones
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Makes sure there are `needed` free bytes, dropping old messages if the policy allows it
+    fn reserve(&mut self, needed: usize) -> bool {
+        if needed > N {
+            // dropping old messages wouldn't make room for this one
+            return false;
+        }
+
+        loop {
+            let read = self.ring.read.load(Ordering::Acquire);
+            if N - LogRing::<N>::used(read, self.write) >= needed {
+                return true;
+            }
+
+            if self.ring.policy == OverflowPolicy::DropNewest
+                || read & CLAIMED != 0
+                || read == self.write
+            {
+                return false;
+            }
+
+            // NOTE there's at least one committed message at `read`
+            let len = unsafe { self.ring.header(read) };
+            let next = LogRing::<N>::wrap(read + HEADER + len);
+            // NOTE fails if the consumer has just released or claimed the message
+            if self
+                .ring
+                .read
+                .compare_exchange(read, next, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                self.dropped = self.dropped.wrapping_add(1);
+            }
+        }
+    }
+}
+
+impl<const N: usize> uWrite for Producer<'_, N> {
+    type Error = Overflow;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Overflow> {
+        if self.failed {
+            return Err(Overflow);
+        }
+
+        let len = self.pending + s.len();
+        if len > usize::from(u16::MAX) || !self.reserve(HEADER + len) {
+            self.failed = true;
+            return Err(Overflow);
+        }
+
+        let pos = LogRing::<N>::wrap(self.write + HEADER + self.pending);
+        // NOTE the space has just been reserved
+        unsafe { self.ring.copy_in(pos, s.as_bytes()) };
+        self.pending = len;
+        Ok(())
+    }
+}
+
+/// The reading half of a [`LogRing`]
+///
+/// Messages are read in contiguous chunks: [`read`](Self::read) returns the next chunk, which
+/// is handed back with [`release`](Self::release). A message that wraps around the end of the
+/// buffer is read in two chunks; a message is never dropped by the producer after its first
+/// chunk has been read.
+pub struct Consumer<'a, const N: usize> {
+    chunk: usize,
+    len: usize,
+    offset: usize,
+    reading: bool,
+    ring: &'a LogRing<N>,
+    start: usize,
+}
+
+impl<const N: usize> Consumer<'_, N> {
+    /// Returns the next chunk of the oldest message, if any
+    ///
+    /// The same chunk is returned until it's released.
+    pub fn read(&mut self) -> Option<&[u8]> {
+        if !self.reading {
+            loop {
+                let read = self.ring.read.load(Ordering::Acquire);
+                if read == self.ring.write.load(Ordering::Acquire) {
+                    return None;
+                }
+
+                // NOTE fails if the producer has just dropped the message
+                if self
+                    .ring
+                    .read
+                    .compare_exchange(read, read | CLAIMED, Ordering::AcqRel, Ordering::Acquire)
+                    .is_ok()
+                {
+                    self.reading = true;
+                    self.start = read;
+                    // NOTE the message has been committed and now it can't be dropped
+                    self.len = unsafe { self.ring.header(read) };
+                    self.offset = 0;
+                    break;
+                }
+            }
+        }
+
+        let pos = LogRing::<N>::wrap(self.start + HEADER + self.offset);
+        let index = if pos >= N { pos - N } else { pos };
+        self.chunk = (self.len - self.offset).min(N - index);
+        // NOTE the chunk belongs to the claimed message
+        Some(unsafe { slice::from_raw_parts(self.ring.ptr(pos), self.chunk) })
+    }
+
+    /// Releases the chunk returned by the last call to [`read`](Self::read)
+    pub fn release(&mut self) {
+        if !self.reading {
+            return;
+        }
+
+        self.offset += self.chunk;
+        self.chunk = 0;
+        if self.offset == self.len {
+            self.reading = false;
+            let next = LogRing::<N>::wrap(self.start + HEADER + self.len);
+            // NOTE the producer doesn't touch `read` while it's claimed
+            self.ring.read.store(next, Ordering::Release);
+        }
+    }
+}
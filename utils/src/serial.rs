@@ -0,0 +1,92 @@
+use ufmt_write::uWrite;
+
+/// An adapter that allows `ufmt` to write into an [`embedded_io::Write`] implementer, like a HAL
+/// UART
+///
+/// Errors of the underlying writer are forwarded as they are.
+#[cfg(feature = "embedded-io")]
+pub struct IoWriteAdapter<W>(pub W)
+where
+    W: embedded_io::Write;
+
+#[cfg(feature = "embedded-io")]
+impl<W> uWrite for IoWriteAdapter<W>
+where
+    W: embedded_io::Write,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        self.0.write_all(s.as_bytes())
+    }
+}
+
+/// An adapter that allows `ufmt` to write into an [`embedded_hal_nb::serial::Write`]
+/// implementer, one byte at a time
+///
+/// Errors of the underlying writer are forwarded as they are. What happens when the serial port
+/// is not ready for the next byte depends on how the adapter was created: a
+/// [`blocking`](Self::blocking) adapter spins until it is, while a
+/// [`non_blocking`](Self::non_blocking) one fails with [`nb::Error::WouldBlock`]; in that case the
+/// rest of the string slice being written is dropped.
+///
+/// [`nb::Error::WouldBlock`]: embedded_hal_nb::nb::Error::WouldBlock
+#[cfg(feature = "embedded-hal-nb")]
+pub struct SerialWriteAdapter<W>
+where
+    W: embedded_hal_nb::serial::Write<u8>,
+{
+    blocking: bool,
+    writer: W,
+}
+
+#[cfg(feature = "embedded-hal-nb")]
+impl<W> SerialWriteAdapter<W>
+where
+    W: embedded_hal_nb::serial::Write<u8>,
+{
+    /// Creates an adapter that spins while the serial port is busy
+    pub fn blocking(writer: W) -> Self {
+        Self {
+            blocking: true,
+            writer,
+        }
+    }
+
+    /// Creates an adapter that fails with `WouldBlock` when the serial port is busy
+    pub fn non_blocking(writer: W) -> Self {
+        Self {
+            blocking: false,
+            writer,
+        }
+    }
+
+    /// Destroys the adapter and returns the underlying writer
+    pub fn free(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(feature = "embedded-hal-nb")]
+impl<W> uWrite for SerialWriteAdapter<W>
+where
+    W: embedded_hal_nb::serial::Write<u8>,
+{
+    type Error = embedded_hal_nb::nb::Error<W::Error>;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        for byte in s.bytes() {
+            loop {
+                match self.writer.write(byte) {
+                    Err(embedded_hal_nb::nb::Error::WouldBlock) if self.blocking => {}
+                    result => {
+                        result?;
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
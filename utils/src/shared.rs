@@ -0,0 +1,166 @@
+use core::{
+    cell::{Cell, RefCell},
+    convert::Infallible,
+};
+
+use critical_section::Mutex;
+use ufmt_write::uWrite;
+
+use crate::Ignore;
+
+/// A writer that can be shared between execution contexts, like interrupt handlers
+///
+/// Access to the writer is serialized with a `critical-section` mutex; `uWrite` is implemented on
+/// `&SharedWriter`, so it can be written to from a `static`. Each `write_str` call is its own
+/// critical section, so use [`lock`](Self::lock) to keep a whole message together.
+///
+/// Writes made while the writer is in use by the same context, for example from a `uDisplay`
+/// implementation called within `lock`, are discarded.
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::SharedWriter;
+///
+/// static LOG: SharedWriter<String> = SharedWriter::new(String::new());
+///
+/// uwrite!(&LOG, "{} ", 1).unwrap();
+/// LOG.lock(|w| uwrite!(w, "{}-{}", 2, 3)).unwrap().unwrap();
+/// assert_eq!(LOG.lock(|w| w.clone()).unwrap(), "1 2-3");
+/// ```
+pub struct SharedWriter<W> {
+    writer: Mutex<RefCell<W>>,
+}
+
+impl<W> SharedWriter<W> {
+    /// Creates a new `SharedWriter`
+    pub const fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(RefCell::new(writer)),
+        }
+    }
+
+    /// Runs `f` with exclusive access to the writer, within a critical section
+    ///
+    /// Returns `None`, without running `f`, if the writer is already in use by this context.
+    pub fn lock<R>(&self, f: impl FnOnce(&mut W) -> R) -> Option<R> {
+        critical_section::with(|cs| {
+            let mut writer = self.writer.borrow(cs).try_borrow_mut().ok()?;
+            Some(f(&mut writer))
+        })
+    }
+
+    /// Destroys the `SharedWriter` and returns the underlying writer
+    pub fn free(self) -> W {
+        self.writer.into_inner().into_inner()
+    }
+}
+
+impl<W> uWrite for &SharedWriter<W>
+where
+    W: uWrite,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        self.lock(|w| w.write_str(s)).unwrap_or(Ok(()))
+    }
+}
+
+/// The writer used by [`uprint!`](crate::uprint) and [`uprintln!`](crate::uprintln)
+static GLOBAL: Mutex<Cell<Option<&'static dyn UnstableGlobalWriter>>> = Mutex::new(Cell::new(None));
+
+#[doc(hidden)]
+pub trait UnstableGlobalWriter: Sync {
+    fn with(&self, f: &mut dyn FnMut(&mut dyn uWrite<Error = Infallible>));
+}
+
+impl<W> UnstableGlobalWriter for SharedWriter<W>
+where
+    W: uWrite + Send,
+{
+    fn with(&self, f: &mut dyn FnMut(&mut dyn uWrite<Error = Infallible>)) {
+        self.lock(|w| f(&mut Ignore::new(w)));
+    }
+}
+
+/// Registers the writer used by [`uprint!`](crate::uprint) and [`uprintln!`](crate::uprintln)
+///
+/// The global writer can only be set once; later calls return the rejected writer.
+pub fn set_global_writer<W>(
+    writer: &'static SharedWriter<W>,
+) -> Result<(), &'static SharedWriter<W>>
+where
+    W: uWrite + Send,
+{
+    critical_section::with(|cs| {
+        let global = GLOBAL.borrow(cs);
+        if global.get().is_some() {
+            return Err(writer);
+        }
+
+        global.set(Some(writer));
+        Ok(())
+    })
+}
+
+#[doc(hidden)]
+pub fn unstable_with_global_writer(f: impl FnOnce(&mut dyn uWrite<Error = Infallible>)) {
+    if let Some(writer) = critical_section::with(|cs| GLOBAL.borrow(cs).get()) {
+        let mut f = Some(f);
+        writer.with(&mut |w| {
+            if let Some(f) = f.take() {
+                f(w)
+            }
+        });
+    }
+}
+
+/// Formats data into the global writer
+///
+/// Does nothing, not even formatting, if no writer has been registered with
+/// [`set_global_writer`](crate::set_global_writer). The message is written within a single
+/// critical section and errors are ignored.
+///
+/// ```
+/// use ufmt_utils::{set_global_writer, uprint, uprintln, SharedWriter};
+///
+/// static UART: SharedWriter<String> = SharedWriter::new(String::new());
+///
+/// uprintln!("lost");
+/// set_global_writer(&UART).ok();
+/// uprint!("{} + {}", 1, 2);
+/// uprintln!(" = {}", 3);
+/// uprintln!();
+/// assert_eq!(UART.lock(|w| w.clone()).unwrap(), "1 + 2 = 3\n\n");
+/// ```
+#[macro_export]
+macro_rules! uprint {
+    // IMPORTANT use `tt` fragments instead of `expr` fragments (i.e. `$($exprs:expr),*`)
+    ($($tt:tt)*) => {
+        $crate::unstable_with_global_writer(|w| {
+            // see `uformat_buf!`
+            use $crate::ufmt;
+
+            let _ = ufmt::uwrite!(w, $($tt)*);
+        })
+    };
+}
+
+/// Formats data, with a newline appended, into the global writer
+///
+/// See [`uprint!`](crate::uprint) for more details
+#[macro_export]
+macro_rules! uprintln {
+    () => {
+        $crate::uprint!("\n")
+    };
+    // IMPORTANT use `tt` fragments instead of `expr` fragments (i.e. `$($exprs:expr),*`)
+    ($($tt:tt)*) => {
+        $crate::unstable_with_global_writer(|w| {
+            // see `uformat_buf!`
+            use $crate::ufmt;
+
+            let _ = ufmt::uwriteln!(w, $($tt)*);
+        })
+    };
+}
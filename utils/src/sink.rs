@@ -0,0 +1,34 @@
+use heapless::Vec;
+
+use crate::Overflow;
+
+/// A sink for encoded output that's not necessarily valid UTF-8
+///
+/// This is the byte-level counterpart of `uWrite`, written into by the adapters that encode text
+/// into binary formats, like [`Cobs`](crate::Cobs).
+pub trait ByteSink {
+    /// The error associated to this sink
+    type Error;
+
+    /// Writes all the `bytes` into this sink
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+impl<S> ByteSink for &mut S
+where
+    S: ByteSink + ?Sized,
+{
+    type Error = S::Error;
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), S::Error> {
+        (**self).write_bytes(bytes)
+    }
+}
+
+impl<const N: usize> ByteSink for Vec<u8, N> {
+    type Error = Overflow;
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Overflow> {
+        self.extend_from_slice(bytes).map_err(|_| Overflow)
+    }
+}
@@ -0,0 +1,222 @@
+use core::mem;
+
+use heapless::String;
+use ufmt_write::uWrite;
+
+use crate::Flush;
+
+/// A write adapter that word-wraps its input at `N` columns
+///
+/// Words are separated by spaces, tabs and newlines; runs of spaces collapse into one and spaces
+/// at the start of a wrapped line are dropped. Words longer than `N` are truncated unless
+/// [`hard_break`](Self::hard_break) is enabled. Columns are counted in `char`s but a word is
+/// buffered in `N` bytes, so non-ASCII words may be treated as long before reaching `N` columns.
+///
+/// Output past the last line allowed by [`max_lines`](Self::max_lines) is discarded and reported
+/// by [`is_overflowed`](Self::is_overflowed). The last word is held back until a separator comes
+/// in, so call [`flush`](Self::flush) or [`free`](Self::free) when done writing. The adapter
+/// doesn't flush on drop; wrap it in a [`FlushOnDrop`](crate::FlushOnDrop) adapter for that.
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{SliceWriter, Wrap};
+///
+/// let mut buf = [0; 32];
+/// let mut w = Wrap::<_, 16>::new(SliceWriter::new(&mut buf)).max_lines(2);
+///
+/// uwrite!(w, "Temperature: {} C, humidity: {}%", 23, 45).unwrap();
+/// w.flush().unwrap();
+/// assert!(!w.is_overflowed());
+///
+/// uwrite!(w, " (ok)").unwrap();
+/// w.flush().unwrap();
+/// assert!(w.is_overflowed());
+/// assert_eq!(w.free().as_str(), "Temperature: 23\nC, humidity: 45%");
+/// ```
+///
+/// Long words can be broken instead of truncated
+///
+/// ```
+/// use ufmt::uwrite;
+/// use ufmt_utils::{SliceWriter, Wrap};
+///
+/// let mut buf = [0; 16];
+/// let mut w = Wrap::<_, 4>::new(SliceWriter::new(&mut buf)).hard_break(true);
+///
+/// uwrite!(w, "abcdefghij k").unwrap();
+/// assert_eq!(w.free().as_str(), "abcd\nefgh\nij k");
+/// ```
+pub struct Wrap<W, const N: usize>
+where
+    W: uWrite,
+{
+    breaks: usize,
+    column: usize,
+    hard_break: bool,
+    line: usize,
+    max_lines: usize,
+    overflowed: bool,
+    skipping: bool,
+    space: bool,
+    word: String<N>,
+    word_chars: usize,
+    writer: W,
+}
+
+impl<W, const N: usize> Wrap<W, N>
+where
+    W: uWrite,
+{
+    /// Creates a new `Wrap` adapter with no line limit that truncates long words
+    pub fn new(writer: W) -> Self {
+        Self {
+            breaks: 0,
+            column: 0,
+            hard_break: false,
+            line: 0,
+            max_lines: usize::MAX,
+            overflowed: false,
+            skipping: false,
+            space: false,
+            word: String::new(),
+            word_chars: 0,
+            writer,
+        }
+    }
+
+    /// Breaks words longer than `N` columns across lines, instead of truncating them
+    pub fn hard_break(mut self, hard_break: bool) -> Self {
+        self.hard_break = hard_break;
+        self
+    }
+
+    /// Limits the output to `lines` lines; at least one line is always written
+    pub fn max_lines(mut self, lines: usize) -> Self {
+        self.max_lines = lines;
+        self
+    }
+
+    /// Returns `true` if some of the data has been discarded because it didn't fit in the allowed
+    /// number of lines
+    pub fn is_overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    /// Writes out the word that's being held back
+    ///
+    /// Trailing newlines are not written until more data comes in.
+    pub fn flush(&mut self) -> Result<(), W::Error> {
+        self.emit_word()
+    }
+
+    /// Flushes the adapter, ignoring errors, then destroys it and returns the underlying writer
+    pub fn free(mut self) -> W {
+        self.flush().ok();
+        self.writer
+    }
+
+    /// Writes `breaks` newlines; returns `false` if the line limit was hit
+    fn break_lines(&mut self, breaks: usize) -> Result<bool, W::Error> {
+        for _ in 0..breaks {
+            if self.line + 1 >= self.max_lines {
+                self.overflowed = true;
+                return Ok(false);
+            }
+
+            self.writer.write_str("\n")?;
+            self.line += 1;
+            self.column = 0;
+        }
+
+        Ok(true)
+    }
+
+    fn emit_word(&mut self) -> Result<(), W::Error> {
+        if self.word.is_empty() {
+            return Ok(());
+        }
+
+        let mut breaks = mem::take(&mut self.breaks);
+        let mut space = mem::take(&mut self.space) && breaks == 0 && self.column != 0;
+        if breaks == 0 && self.column != 0 && self.column + space as usize + self.word_chars > N {
+            breaks = 1;
+            space = false;
+        }
+
+        if self.break_lines(breaks)? {
+            if space {
+                self.writer.write_str(" ")?;
+                self.column += 1;
+            }
+
+            self.writer.write_str(&self.word)?;
+            self.column += self.word_chars;
+        }
+
+        self.word.clear();
+        self.word_chars = 0;
+        Ok(())
+    }
+}
+
+impl<W, const N: usize> Flush for Wrap<W, N>
+where
+    W: uWrite,
+{
+    fn flush(&mut self) -> Result<(), W::Error> {
+        Wrap::flush(self)
+    }
+}
+
+impl<W, const N: usize> uWrite for Wrap<W, N>
+where
+    W: uWrite,
+{
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        for c in s.chars() {
+            if self.overflowed {
+                break;
+            }
+
+            match c {
+                '\n' => {
+                    self.emit_word()?;
+                    self.breaks += 1;
+                    self.space = false;
+                    self.skipping = false;
+                }
+
+                ' ' | '\t' => {
+                    self.emit_word()?;
+                    self.space = true;
+                    self.skipping = false;
+                }
+
+                _ if self.skipping => {}
+
+                _ => {
+                    if self.word_chars == N || self.word.push(c).is_err() {
+                        // the word doesn't fit in a line
+                        self.emit_word()?;
+
+                        if !self.hard_break {
+                            self.skipping = true;
+                            continue;
+                        }
+
+                        if self.word.push(c).is_err() {
+                            // `N` is too small to hold `c`
+                            continue;
+                        }
+                    }
+
+                    self.word_chars += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
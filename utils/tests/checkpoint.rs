@@ -0,0 +1,40 @@
+use heapless::String;
+use ufmt::{uWriteCheckpoint, uwrite, uwrite_atomic};
+use ufmt_utils::{Overflow, SliceWriter};
+
+#[test]
+fn slice_writer() {
+    let mut buf = [0; 8];
+    let mut w = SliceWriter::new(&mut buf);
+
+    uwrite!(w, "μ").unwrap();
+    // not a `char` boundary
+    w.rollback(1);
+    assert_eq!(w.as_str(), "μ");
+    // past the end
+    w.rollback(3);
+    assert_eq!(w.as_str(), "μ");
+
+    let checkpoint = w.checkpoint();
+    uwrite!(w, "fmt").unwrap();
+    w.rollback(checkpoint);
+    assert_eq!(w.as_str(), "μ");
+
+    assert_eq!(uwrite_atomic!(w, "{} {}", 1, 2345678), Err(Overflow));
+    assert_eq!(w.into_str(), "μ");
+}
+
+#[test]
+fn heapless_string() {
+    let mut s = String::<8>::new();
+
+    uwrite!(s, "μ").unwrap();
+    s.rollback(1);
+    assert_eq!(s, "μ");
+
+    assert_eq!(uwrite_atomic!(s, "{} {}", 1, 2345678), Err(()));
+    assert_eq!(s, "μ");
+
+    uwrite_atomic!(s, "{}", 42).unwrap();
+    assert_eq!(s, "μ42");
+}
@@ -0,0 +1,115 @@
+use heapless::Vec;
+use ufmt::uwrite;
+use ufmt_utils::{Cobs, CobsDecoder, CobsError, DecodeError};
+
+type Sink = Vec<u8, 2048>;
+
+fn decode(encoded: &[u8]) -> std::vec::Vec<Result<std::vec::Vec<u8>, DecodeError>> {
+    let mut decoder = CobsDecoder::<1024>::new();
+    let mut frames = vec![];
+    for byte in encoded {
+        match decoder.feed(*byte) {
+            Ok(Some(frame)) => frames.push(Ok(frame.to_vec())),
+            Ok(None) => {}
+            Err(e) => frames.push(Err(e)),
+        }
+    }
+    frames
+}
+
+fn round_trip(packet: &str) {
+    let mut w = Cobs::<_, 254>::new(Sink::new());
+    w.frame(|w| uwrite!(w, "{}", packet)).unwrap();
+    let encoded = w.free();
+
+    assert_eq!(encoded.iter().filter(|b| **b == 0).count(), 1);
+    assert_eq!(encoded.last(), Some(&0));
+    assert_eq!(decode(&encoded), [Ok(packet.as_bytes().to_vec())]);
+}
+
+#[test]
+fn run_boundaries() {
+    for len in [1, 2, 253, 254, 255, 507, 508, 509] {
+        let run = "a".repeat(len);
+
+        round_trip(&run);
+        round_trip(&format!("{}\0", run));
+        round_trip(&format!("\0{}", run));
+        round_trip(&format!("{}\0{}", run, run));
+    }
+
+    round_trip("\0");
+    round_trip("\0\0");
+}
+
+#[test]
+fn split_writes() {
+    // a packet written in many pieces encodes like the whole packet
+    let mut w = Cobs::<_, 254>::new(Sink::new());
+    w.frame(|w| {
+        for _ in 0..300 {
+            uwrite!(w, "a")?;
+        }
+        Ok(())
+    })
+    .unwrap();
+
+    let mut whole = Cobs::<_, 254>::new(Sink::new());
+    whole
+        .frame(|w| uwrite!(w, "{}", "a".repeat(300).as_str()))
+        .unwrap();
+
+    assert_eq!(w.free(), whole.free());
+}
+
+#[test]
+fn empty_packet() {
+    let mut w = Cobs::<_, 254>::new(Sink::new());
+    w.finish().unwrap();
+    w.frame(|w| uwrite!(w, "x")).unwrap();
+    let encoded = w.free();
+
+    assert_eq!(encoded, b"\x01\x00\x02x\x00");
+    // the empty packet is skipped
+    assert_eq!(decode(&encoded), [Ok(b"x".to_vec())]);
+}
+
+#[test]
+fn overflow() {
+    let mut w = Cobs::<_, 4>::new(Sink::new());
+
+    // the packet is discarded, including the part that was already written into the sink
+    assert_eq!(
+        w.frame(|w| uwrite!(w, "ab\0cdefgh")),
+        Err(CobsError::Overflow)
+    );
+    // and the next packet is not affected
+    w.frame(|w| uwrite!(w, "ok")).unwrap();
+
+    // nothing was written into the sink before the overflow
+    assert_eq!(w.frame(|w| uwrite!(w, "abcde")), Err(CobsError::Overflow));
+
+    let encoded = w.free();
+    assert_eq!(
+        decode(&encoded),
+        [Err(DecodeError::Malformed), Ok(b"ok".to_vec())]
+    );
+}
+
+#[test]
+fn overflow_without_frame() {
+    let mut w = Cobs::<_, 4>::new(Sink::new());
+
+    assert_eq!(uwrite!(w, "a\0bcdefgh"), Err(CobsError::Overflow));
+    // the rest of the packet is discarded
+    assert_eq!(uwrite!(w, "x"), Err(CobsError::Overflow));
+    assert_eq!(w.finish(), Err(CobsError::Overflow));
+
+    uwrite!(w, "y").unwrap();
+    w.finish().unwrap();
+
+    assert_eq!(
+        decode(&w.free()),
+        [Err(DecodeError::Malformed), Ok(b"y".to_vec())]
+    );
+}
@@ -0,0 +1,98 @@
+use std::thread;
+
+use ufmt::{uwrite, uwriteln};
+use ufmt_utils::{LogRing, OverflowPolicy};
+
+const MESSAGES: u32 = 10_000;
+
+fn drain<const N: usize>(ring: &mut LogRing<N>, policy_drops: bool) {
+    let (mut producer, mut consumer) = ring.split();
+
+    let buffer = thread::scope(|s| {
+        let handle = s.spawn(move || {
+            for i in 0..MESSAGES {
+                // retry until there's room, unless messages may be dropped
+                while producer.message(|w| uwriteln!(w, "{} {}", i, "μ")).is_err() && !policy_drops
+                {
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut buffer = Vec::new();
+        loop {
+            let finished = handle.is_finished();
+            match consumer.read() {
+                Some(chunk) => {
+                    buffer.extend_from_slice(chunk);
+                    consumer.release();
+                }
+                None if finished => break,
+                None => thread::yield_now(),
+            }
+        }
+
+        handle.join().unwrap();
+        buffer
+    });
+
+    let buffer = String::from_utf8(buffer).unwrap();
+    let lines = buffer.lines().collect::<Vec<_>>();
+
+    // messages are whole and in order, even when some are dropped
+    let mut last = None;
+    for line in &lines {
+        let (i, mu) = line.split_once(' ').unwrap();
+        let i: u32 = i.parse().unwrap();
+        assert_eq!(mu, "μ");
+        assert!(last < Some(i));
+        last = Some(i);
+    }
+
+    if !policy_drops {
+        assert_eq!(lines.len(), MESSAGES as usize);
+    }
+}
+
+#[test]
+fn drop_newest() {
+    drain(&mut LogRing::<64>::new(OverflowPolicy::DropNewest), false);
+}
+
+#[test]
+fn overwrite_oldest() {
+    drain(
+        &mut LogRing::<64>::new(OverflowPolicy::OverwriteOldest),
+        true,
+    );
+}
+
+#[test]
+fn too_long() {
+    let mut ring = LogRing::<8>::new(OverflowPolicy::OverwriteOldest);
+    let (mut producer, mut consumer) = ring.split();
+
+    producer.message(|w| uwrite!(w, "ok")).unwrap();
+    assert!(producer.message(|w| uwrite!(w, "{}", 1234567)).is_err());
+    // only the message that can never fit is dropped
+    assert_eq!(producer.dropped(), 1);
+    assert_eq!(consumer.read(), Some(&b"ok"[..]));
+}
+
+#[test]
+fn wrap_around() {
+    let mut ring = LogRing::<8>::new(OverflowPolicy::DropNewest);
+    let (mut producer, mut consumer) = ring.split();
+
+    producer.message(|w| uwrite!(w, "abc")).unwrap();
+    assert_eq!(consumer.read(), Some(&b"abc"[..]));
+    consumer.release();
+
+    // starts at position 5 so it wraps around
+    producer.message(|w| uwrite!(w, "defg")).unwrap();
+    assert_eq!(consumer.read(), Some(&b"d"[..]));
+    consumer.release();
+    assert_eq!(consumer.read(), Some(&b"efg"[..]));
+    consumer.release();
+    assert_eq!(consumer.read(), None);
+}
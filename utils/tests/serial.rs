@@ -0,0 +1,103 @@
+use embedded_hal_nb::{nb, serial};
+use ufmt::uwrite;
+use ufmt_utils::{IoWriteAdapter, SerialWriteAdapter};
+
+/// A writer that takes at most 3 bytes per `write` and holds at most `capacity` bytes
+struct MockIo {
+    capacity: usize,
+    written: Vec<u8>,
+}
+
+impl embedded_io::ErrorType for MockIo {
+    type Error = embedded_io::ErrorKind;
+}
+
+impl embedded_io::Write for MockIo {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        let n = buf.len().min(3).min(self.capacity - self.written.len());
+        if n == 0 {
+            return Err(embedded_io::ErrorKind::OutOfMemory);
+        }
+
+        self.written.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// A serial port that is busy on every other `write` and fails on the byte `!`
+struct MockSerial {
+    busy: bool,
+    written: Vec<u8>,
+}
+
+impl serial::ErrorType for MockSerial {
+    type Error = serial::ErrorKind;
+}
+
+impl serial::Write<u8> for MockSerial {
+    fn write(&mut self, word: u8) -> nb::Result<(), Self::Error> {
+        self.busy = !self.busy;
+        if !self.busy {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        if word == b'!' {
+            return Err(nb::Error::Other(serial::ErrorKind::Overrun));
+        }
+
+        self.written.push(word);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn embedded_io() {
+    let mut w = IoWriteAdapter(MockIo {
+        capacity: 16,
+        written: vec![],
+    });
+
+    uwrite!(w, "{:?} μ", [1, 20, 300]).unwrap();
+    assert_eq!(w.0.written, "[1, 20, 300] μ".as_bytes());
+
+    assert_eq!(
+        uwrite!(w, "{}", 4000),
+        Err(embedded_io::ErrorKind::OutOfMemory)
+    );
+}
+
+#[test]
+fn embedded_hal_nb_blocking() {
+    let mut w = SerialWriteAdapter::blocking(MockSerial {
+        busy: false,
+        written: vec![],
+    });
+
+    uwrite!(w, "{} μ", 42).unwrap();
+    assert_eq!(
+        uwrite!(w, "!"),
+        Err(nb::Error::Other(serial::ErrorKind::Overrun))
+    );
+    assert_eq!(w.free().written, "42 μ".as_bytes());
+}
+
+#[test]
+fn embedded_hal_nb_non_blocking() {
+    let mut w = SerialWriteAdapter::non_blocking(MockSerial {
+        busy: false,
+        written: vec![],
+    });
+
+    uwrite!(w, "a").unwrap();
+    assert_eq!(uwrite!(w, "bc"), Err(nb::Error::WouldBlock));
+    assert_eq!(uwrite!(w, "d"), Ok(()));
+    assert_eq!(w.free().written, b"ad");
+}
@@ -0,0 +1,33 @@
+use heapless::String;
+
+use crate::{uWrite, uWriteCheckpoint};
+
+// NOTE heapless' own `ufmt` feature implements v0.1.x of the `uWrite` trait
+impl<const N: usize> uWrite for String<N> {
+    type Error = ();
+
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), ()> {
+        self.push_str(s)
+    }
+}
+
+impl<const N: usize> uWriteCheckpoint for String<N> {
+    type Checkpoint = usize;
+
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len()
+    }
+
+    /// Discards the data written since `len`
+    ///
+    /// A `len` that is past the end of the string, or that doesn't fall on a `char` boundary of
+    /// it, is ignored.
+    #[inline]
+    fn rollback(&mut self, len: usize) {
+        if len < self.len() && self.is_char_boundary(len) {
+            self.truncate(len)
+        }
+    }
+}
@@ -6,6 +6,8 @@
 #![deny(rust_2018_idioms)]
 #![deny(warnings)]
 
+use core::any::{Any, TypeId};
+
 /// A collection of methods that are required / used to format a message into a stream.
 #[allow(non_camel_case_types)]
 pub trait uWrite {
@@ -27,7 +29,111 @@ pub trait uWrite {
     fn write_char(&mut self, c: char) -> Result<(), Self::Error> {
         self.write_str(c.encode_utf8(&mut [0; 4]))
     }
+
+    // Implementation detail of `ufmt`'s formatter context; do NOT override
+    #[doc(hidden)]
+    #[inline(always)]
+    fn unstable_context(&self, _type_id: TypeId) -> Option<&dyn Any> {
+        None
+    }
+}
+
+// NOTE this blanket implementation was added in v0.2.0; it conflicts with implementations of
+// `uWrite` for `&mut T` (where `T` is a local type) that compiled on v0.1.x
+impl<W> uWrite for &mut W
+where
+    W: uWrite + ?Sized,
+{
+    type Error = W::Error;
+
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        (**self).write_str(s)
+    }
+
+    #[inline]
+    fn write_char(&mut self, c: char) -> Result<(), W::Error> {
+        (**self).write_char(c)
+    }
+
+    #[inline(always)]
+    fn unstable_context(&self, type_id: TypeId) -> Option<&dyn Any> {
+        (**self).unstable_context(type_id)
+    }
+}
+
+/// A writer whose output can be rolled back to an earlier state
+///
+/// This is used by `ufmt::uwrite_atomic!` to avoid leaving a partially formatted message in the
+/// writer when one of its pieces fails to be written.
+#[allow(non_camel_case_types)]
+pub trait uWriteCheckpoint: uWrite {
+    /// A snapshot of the writer state
+    type Checkpoint;
+
+    /// Captures the current state of the writer
+    fn checkpoint(&self) -> Self::Checkpoint;
+
+    /// Discards everything written since `checkpoint` was captured
+    ///
+    /// Writers that forward data to another writer can only discard the data they still hold.
+    fn rollback(&mut self, checkpoint: Self::Checkpoint);
+}
+
+impl<W> uWriteCheckpoint for &mut W
+where
+    W: uWriteCheckpoint + ?Sized,
+{
+    type Checkpoint = W::Checkpoint;
+
+    #[inline]
+    fn checkpoint(&self) -> W::Checkpoint {
+        (**self).checkpoint()
+    }
+
+    #[inline]
+    fn rollback(&mut self, checkpoint: W::Checkpoint) {
+        (**self).rollback(checkpoint)
+    }
+}
+
+/// An asynchronous counterpart of [`uWrite`], for writers like async UART drivers
+///
+/// This is used by `ufmt::uwrite_async!`, which formats the message in small chunks and awaits
+/// the writer after each one.
+#[allow(async_fn_in_trait)]
+#[allow(non_camel_case_types)]
+pub trait uWriteAsync {
+    /// The error associated to this writer
+    type Error;
+
+    /// Writes a string slice into this writer, returning whether the write succeeded.
+    ///
+    /// Like [`uWrite::write_str`], this can only succeed if the entire string slice was written.
+    async fn write_str(&mut self, s: &str) -> Result<(), Self::Error>;
+
+    /// Writes a [`char`] into this writer, returning whether the write succeeded.
+    async fn write_char(&mut self, c: char) -> Result<(), Self::Error> {
+        self.write_str(c.encode_utf8(&mut [0; 4])).await
+    }
+}
+
+impl<W> uWriteAsync for &mut W
+where
+    W: uWriteAsync + ?Sized,
+{
+    type Error = W::Error;
+
+    async fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        (**self).write_str(s).await
+    }
+
+    async fn write_char(&mut self, c: char) -> Result<(), W::Error> {
+        (**self).write_char(c).await
+    }
 }
 
+#[cfg(feature = "heapless")]
+mod heapless;
 #[cfg(feature = "std")]
 mod std;
@@ -1,6 +1,6 @@
 use std::{collections::TryReserveError, ptr};
 
-use crate::uWrite;
+use crate::{uWrite, uWriteCheckpoint};
 
 #[cfg(feature = "std")]
 impl uWrite for String {
@@ -19,3 +19,18 @@ impl uWrite for String {
         Ok(())
     }
 }
+
+#[cfg(feature = "std")]
+impl uWriteCheckpoint for String {
+    type Checkpoint = usize;
+
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn rollback(&mut self, len: usize) {
+        self.truncate(len)
+    }
+}